@@ -0,0 +1,143 @@
+use crate::new_suffix_array::InnovativeSuffixArrayBuilder;
+use crate::prefix_tree::symbol::Symbol;
+
+// BURROWS-WHEELER TRANSFORM
+// Computed over "str + $", where "$" is a Sentinel smaller than any other Symbol. The Sentinel
+// is not stored: "symbols" only holds the other "str.len()" Symbols of the last column, and
+// "primary_index" is the row where the Sentinel stands (as in the "primary index" formulation).
+// From string "banana":
+//   rows (sorted)  = [$banana, a$banan, ana$ban, anana$b, banana$, na$bana, nana$ba]
+//   last column    = "annb$aa"
+//   symbols        = "annbaa"
+//   primary_index  = 4
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bwt<T> {
+    pub symbols: Vec<T>,
+    pub primary_index: usize,
+}
+impl<T: Copy + PartialEq> Bwt<T> {
+    pub fn len_with_sentinel(&self) -> usize {
+        self.symbols.len() + 1
+    }
+    // Symbol in the last column at the given row, None for the Sentinel.
+    pub fn get(&self, row: usize) -> Option<T> {
+        if row < self.primary_index {
+            Some(self.symbols[row])
+        } else if row == self.primary_index {
+            None
+        } else {
+            Some(self.symbols[row - 1])
+        }
+    }
+    pub fn to_vec_with_sentinel(&self, sentinel: T) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len_with_sentinel());
+        result.extend_from_slice(&self.symbols[..self.primary_index]);
+        result.push(sentinel);
+        result.extend_from_slice(&self.symbols[self.primary_index..]);
+        result
+    }
+    pub fn from_vec_with_sentinel(symbols_with_sentinel: &[T], sentinel: T) -> Self {
+        // The Sentinel is expected exactly once.
+        let primary_index = symbols_with_sentinel
+            .iter()
+            .position(|&symbol| symbol == sentinel)
+            .expect("BWT without Sentinel");
+        let mut symbols = Vec::with_capacity(symbols_with_sentinel.len() - 1);
+        symbols.extend_from_slice(&symbols_with_sentinel[..primary_index]);
+        symbols.extend_from_slice(&symbols_with_sentinel[primary_index + 1..]);
+        Self {
+            symbols,
+            primary_index,
+        }
+    }
+}
+
+pub fn bwt<T: Symbol>(str: &[T], chunk_size: Option<usize>) -> Bwt<T> {
+    if str.is_empty() {
+        // Only the Sentinel: nothing to factorize.
+        return Bwt {
+            symbols: Vec::new(),
+            primary_index: 0,
+        };
+    }
+    let suffix_array = InnovativeSuffixArrayBuilder::new(str)
+        .chunk_size(chunk_size)
+        .compute()
+        .suffix_array;
+    bwt_from_suffix_array(str, &suffix_array)
+}
+
+pub fn bwt_from_suffix_array<T: Copy>(str: &[T], suffix_array: &[usize]) -> Bwt<T> {
+    // Row 0 is always the Suffix "$", preceded by the last Symbol. Then, Row "i + 1" is the
+    // Suffix "suffix_array[i]", preceded by the Sentinel only if it's the whole string.
+    let str_length = str.len();
+    let mut symbols = Vec::with_capacity(str_length);
+    let mut primary_index = 0;
+    if str_length > 0 {
+        symbols.push(str[str_length - 1]);
+    }
+    for (i, &gs_index) in suffix_array.iter().enumerate() {
+        if gs_index > 0 {
+            symbols.push(str[gs_index - 1]);
+        } else {
+            primary_index = i + 1;
+        }
+    }
+    Bwt {
+        symbols,
+        primary_index,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bwt::inverse::{inverse_bwt, inverse_bwt_with_sentinel};
+    use crate::test_utils::{for_each_random_string, get_repetitive_strings};
+    use rand::Rng;
+
+    fn assert_round_trip(str: &[u8], chunk_size: Option<usize>) {
+        let transformed = bwt(str, chunk_size);
+        assert_eq!(transformed.symbols.len(), str.len());
+        assert_eq!(
+            inverse_bwt(&transformed),
+            str,
+            "str={:?}, chunk_size={chunk_size:?}",
+            String::from_utf8_lossy(str)
+        );
+        let with_sentinel = transformed.to_vec_with_sentinel(b'$');
+        assert_eq!(inverse_bwt_with_sentinel(&with_sentinel, b'$'), str);
+    }
+
+    #[test]
+    fn bwt_known_example() {
+        let transformed = bwt(b"banana", None);
+        assert_eq!(transformed.to_vec_with_sentinel(b'$'), b"annb$aa");
+        assert_eq!(transformed.primary_index, 4);
+        assert_eq!(transformed.get(4), None);
+        assert_eq!(transformed.get(5), Some(b'a'));
+    }
+
+    #[test]
+    fn bwt_empty_string() {
+        let transformed = bwt::<u8>(b"", Some(3));
+        assert_eq!(transformed.len_with_sentinel(), 1);
+        assert!(inverse_bwt(&transformed).is_empty());
+    }
+
+    #[test]
+    fn bwt_round_trip_on_random_strings() {
+        for_each_random_string(5, 200, 1..=100, 1..=4, |str, rng| {
+            let chunk_size = rng.random_range(1..=8);
+            assert_round_trip(str, None);
+            assert_round_trip(str, Some(chunk_size));
+        });
+    }
+
+    #[test]
+    fn bwt_round_trip_on_repetitive_strings() {
+        for str in get_repetitive_strings(30) {
+            assert_round_trip(&str, Some(3));
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::bwt::bwt::Bwt;
+
+pub fn inverse_bwt<T: Ord + Copy>(bwt: &Bwt<T>) -> Vec<T> {
+    let lf = get_lf_mapping(bwt);
+
+    // Row 0 is the Suffix "$": its last column Symbol is the last one of the string, then
+    // LF-Mapping goes one Symbol backwards at a time.
+    let str_length = bwt.symbols.len();
+    let mut result = Vec::with_capacity(str_length);
+    let mut row = 0;
+    for _ in 0..str_length {
+        result.push(bwt.get(row).unwrap());
+        row = lf[row];
+    }
+    result.reverse();
+    result
+}
+
+pub fn inverse_bwt_with_sentinel<T: Ord + Copy>(
+    symbols_with_sentinel: &[T],
+    sentinel: T,
+) -> Vec<T> {
+    inverse_bwt(&Bwt::from_vec_with_sentinel(
+        symbols_with_sentinel,
+        sentinel,
+    ))
+}
+
+pub fn get_lf_mapping<T: Ord + Copy>(bwt: &Bwt<T>) -> Vec<usize> {
+    // LF-Mapping: row in the first column of the Symbol at the given row in the last column.
+    // Stable sorting the last column gives the first column, with equal Symbols keeping their
+    // relative order; the Sentinel is the smallest one.
+    let mut rows = (0..bwt.len_with_sentinel()).collect::<Vec<_>>();
+    rows.sort_by_key(|&row| bwt.get(row));
+    let mut lf = vec![0; rows.len()];
+    for (first_column_row, &last_column_row) in rows.iter().enumerate() {
+        lf[last_column_row] = first_column_row;
+    }
+    lf
+}
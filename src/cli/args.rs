@@ -0,0 +1,109 @@
+// COMMAND LINE ARGUMENTS
+// Positional arguments and "--name value", "--name=value" or "--flag" options. Which options
+// take a value is up to the command, so that flags never swallow the next argument. Any other
+// option is rejected.
+pub struct Args {
+    positionals: Vec<String>,
+    options: Vec<(String, Option<String>)>,
+}
+impl Args {
+    pub fn parse(
+        args: &[String],
+        value_options: &[&str],
+        flag_options: &[&str],
+    ) -> Result<Self, String> {
+        let mut positionals = Vec::new();
+        let mut options = Vec::new();
+        let mut i = 0;
+        while i < args.len() {
+            let arg = &args[i];
+            if let Some(option) = arg.strip_prefix("--") {
+                if let Some((name, value)) = option.split_once('=') {
+                    if flag_options.contains(&name) {
+                        return Err(format!("Option \"--{}\" takes no value", name));
+                    }
+                    if !value_options.contains(&name) {
+                        return Err(format!("Unknown option \"--{}\"", name));
+                    }
+                    options.push((name.to_string(), Some(value.to_string())));
+                } else if value_options.contains(&option) {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(format!("Missing value for \"--{}\"", option));
+                    }
+                    options.push((option.to_string(), Some(args[i].clone())));
+                } else if flag_options.contains(&option) {
+                    options.push((option.to_string(), None));
+                } else {
+                    return Err(format!("Unknown option \"--{}\"", option));
+                }
+            } else {
+                positionals.push(arg.clone());
+            }
+            i += 1;
+        }
+        Ok(Self {
+            positionals,
+            options,
+        })
+    }
+    pub fn get_positional(&self, i: usize, name: &str) -> Result<&str, String> {
+        self.positionals
+            .get(i)
+            .map(|positional| positional.as_str())
+            .ok_or(format!("Missing argument <{}>", name))
+    }
+    pub fn get_option(&self, name: &str) -> Option<&str> {
+        // The last one wins.
+        self.options
+            .iter()
+            .rev()
+            .find(|(option_name, _)| option_name == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+    pub fn get_option_parsed<T: std::str::FromStr>(
+        &self,
+        name: &str,
+        default: T,
+    ) -> Result<T, String> {
+        match self.get_option(name) {
+            Some(value) => value
+                .replace('_', "")
+                .parse::<T>()
+                .map_err(|_| format!("Invalid value \"{}\" for \"--{}\"", value, name)),
+            None => Ok(default),
+        }
+    }
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.options
+            .iter()
+            .any(|(option_name, value)| option_name == name && value.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        Args::parse(&args, &["chunk-size"], &["no-plot"])
+    }
+
+    #[test]
+    fn parse_positionals_values_and_flags() {
+        let args = parse(&["000", "--chunk-size", "3", "--no-plot", "--chunk-size=4"]).unwrap();
+        assert_eq!(args.get_positional(0, "file").unwrap(), "000");
+        assert!(args.get_positional(1, "other").is_err());
+        assert_eq!(args.get_option("chunk-size"), Some("4"));
+        assert!(args.has_flag("no-plot"));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_options() {
+        assert!(parse(&["000", "--chunk-sise", "3"]).is_err());
+        assert!(parse(&["000", "--chunk-sise=3"]).is_err());
+        assert!(parse(&["000", "--no-plot=yes"]).is_err());
+        assert!(parse(&["000", "--chunk-size"]).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+// CHUNK SIZE EXPRESSIONS
+// Comma-separated items, kept in the given order:
+// * "none"     => No Chunk Size (only ICFL Factors);
+// * "N"        => Chunk Size N;
+// * "A..B"     => from A to B, both included;
+// * "A..B:S"   => from A to B (excluded) with steps of S, so that "10..100:10,100..1000:100"
+//                 does not repeat 100.
+// For example: "2..9,10..100:10,none" => [2, 3, ..., 9, 10, 20, ..., 90, none].
+// Chunk Sizes can't be zero, neither alone nor as the start of a range.
+pub fn parse_chunk_sizes(expression: &str) -> Result<Vec<Option<usize>>, String> {
+    let mut result = Vec::new();
+    for item in expression.split(',') {
+        let item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        if item == "none" {
+            result.push(None);
+        } else if let Some((min, rest)) = item.split_once("..") {
+            let min = parse_chunk_size(min)?;
+            if let Some((max_excl, step)) = rest.split_once(':') {
+                let max_excl = parse_number(max_excl)?;
+                let step = parse_number(step)?;
+                if step == 0 {
+                    return Err(format!("Step can't be zero in \"{}\"", item));
+                }
+                result.append(&mut create_chunk_size_of_steps(min, max_excl, step));
+            } else {
+                let max = parse_number(rest)?;
+                result.append(&mut create_chunk_size_interval(min, max));
+            }
+        } else {
+            result.push(Some(parse_chunk_size(item)?));
+        }
+    }
+    if result.is_empty() {
+        return Err(format!("No chunk size in \"{}\"", expression));
+    }
+    Ok(result)
+}
+
+fn parse_chunk_size(str: &str) -> Result<usize, String> {
+    let chunk_size = parse_number(str)?;
+    if chunk_size == 0 {
+        return Err(format!("Chunk size can't be zero in \"{}\"", str.trim()));
+    }
+    Ok(chunk_size)
+}
+
+fn parse_number(str: &str) -> Result<usize, String> {
+    // Allows "_" as in "56_137".
+    str.trim()
+        .replace('_', "")
+        .parse::<usize>()
+        .map_err(|_| format!("Invalid chunk size \"{}\"", str))
+}
+
+pub fn create_chunk_size_interval(min: usize, max: usize) -> Vec<Option<usize>> {
+    (min..=max).map(Some).collect()
+}
+
+pub fn create_chunk_size_of_steps(min: usize, max_excl: usize, step: usize) -> Vec<Option<usize>> {
+    let mut result = Vec::new();
+    let mut curr = min;
+    while curr < max_excl {
+        result.push(Some(curr));
+        curr += step;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_items_and_ranges() {
+        assert_eq!(
+            parse_chunk_sizes("none, 3,5..7,10..40:10,1_000").unwrap(),
+            vec![
+                None,
+                Some(3),
+                Some(5),
+                Some(6),
+                Some(7),
+                Some(10),
+                Some(20),
+                Some(30),
+                Some(1000)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_zero() {
+        assert!(parse_chunk_sizes("0").is_err());
+        assert!(parse_chunk_sizes("2,0").is_err());
+        assert!(parse_chunk_sizes("0..4").is_err());
+        assert!(parse_chunk_sizes("0..10:2").is_err());
+        assert!(parse_chunk_sizes("1..10:0").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_invalid() {
+        assert!(parse_chunk_sizes("").is_err());
+        assert!(parse_chunk_sizes("abc").is_err());
+        assert!(parse_chunk_sizes("1..x").is_err());
+    }
+}
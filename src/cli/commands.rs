@@ -0,0 +1,534 @@
+use crate::cli::args::Args;
+use crate::cli::chunk_sizes::parse_chunk_sizes;
+use crate::extra::suites::generation::main_generation;
+use crate::factorization::auto_chunk_size::AutoChunkSize;
+use crate::factorization::custom_factorization::{Chunking, SplitPolicy};
+use crate::factorization::factorizer::FactorizationStrategy;
+use crate::factorization::logging::log_factorization;
+use crate::factorization::stats::compute_factorization_stats;
+use crate::files::fasta::{get_fasta_content, FastaRecordSelection, FastaSelection};
+use crate::files::json::{dump_json_in_file, read_json_from_file};
+use crate::files::paths::{
+    get_path_for_project_factorization_file, get_path_for_project_factorization_stats_file_json,
+    get_path_for_project_folder, get_path_for_project_suffix_array_file,
+    get_path_for_project_timing_file_json, get_path_in_generated_folder, DEFAULT_PLOTS_FOLDER,
+    DEFAULT_RESULTS_FOLDER,
+};
+use crate::files::results::ResultsFolderLogger;
+use crate::new_suffix_array::InnovativeSuffixArrayBuilder;
+use crate::plot::plot::draw_plot_from_monitor;
+use crate::prefix_tree::layout::TreeLayout;
+use crate::prefix_tree::log_execution_info::ExecutionInfoFileFormat;
+use crate::prefix_tree::monitor::RulesOracleReport;
+use crate::suffix_array::baseline::BaselineSaca;
+use crate::suffix_array::logger::{log_suffix_array, make_sure_directory_exist, read_suffix_array};
+use crate::suffix_array::verifier::verify_suffix_array;
+use crate::suite::{full_suite, print_duration};
+use crate::suite_config::SuiteConfig;
+use crate::suite_stats::PlotStatistic;
+
+const USAGE: &str = "\
+USAGE: ptsaca <COMMAND> <FASTA FILE NAME> [OPTIONS]
+FASTA files are read from \"generated/<FASTA FILE NAME>.fasta\", only the first record by default.
+
+COMMANDS:
+  build      Compute the Suffix Array and write it in a file
+               --chunk-size <CHUNK SIZE>  (default: none)
+               --chunk-threshold <SIZE>   (default: none, only longer Factors are chunked)
+               --split-policy <POLICY>    (default: remainder_first, or: remainder_last, balanced,
+                                           lyndon_boundaries, proportional)
+               --auto-chunk-size <MODE>   (default: none, or: heuristic, dry_run)
+               --factorization <FACT>     (default: icfl, or: cfl, icfl_cfl, cfl_icfl)
+               --output <PATH>            (default: in \"results/\")
+               --threads <NUMBER>         (default: 1, threads for Phase 3)
+               --tree-threads <NUMBER>    (default: 1, threads for Phase 2)
+               --tree-layout <LAYOUT>     (default: nested, or arena)
+               --rules-oracle             (check every Rules decision, mismatches in \"results/\")
+               --log-execution
+  suite      Run the Baselines (Classic by default) vs Innovative benchmark
+               --config <PATH>              (JSON suite config, e.g. \"suites/002_70.json\")
+               --chunk-sizes <CHUNK SIZES>  (required without \"--config\")
+               --chunk-threshold <SIZE>     (default: none, only longer Factors are chunked)
+               --split-policies <POLICIES>  (default: remainder_first, or: remainder_last, balanced,
+                                             lyndon_boundaries, proportional)
+               --auto-chunk-size <MODE>     (default: none, or: heuristic, dry_run, compared
+                                             with the best of the Chunk Sizes)
+               --max-duration <MICROS>      (default: 1_000_000, plot height)
+               --baselines <BASELINES>      (default: classic, or: sa_is, prefix_doubling, dc3)
+               --attempts <NUMBER>          (default: 1)
+               --warm-up <NUMBER>           (default: 0, attempts not measured)
+               --threads <NUMBER>           (default: 1, threads for Phase 3)
+               --tree-threads <NUMBER>      (default: 1, threads for Phase 2)
+               --tree-layouts <LAYOUTS>     (default: nested, or: arena, e.g. \"nested,arena\")
+               --factorizations <FACTS>     (default: icfl, or: cfl, icfl_cfl, cfl_icfl)
+               --outlier-iqr <FACTOR>       (default: none, e.g. 1.5 for Tukey's fences)
+               --plot-statistic <STAT>      (default: mean, or median)
+               --log-execution --log-fact --log-trees --no-plot
+  factorize  Write ICFL and Custom Factors in \"results/\"
+               --chunk-size <CHUNK SIZE>  (default: none)
+               --chunk-threshold <SIZE>   (default: none, only longer Factors are chunked)
+               --split-policy <POLICY>    (default: remainder_first, or: remainder_last, balanced,
+                                           lyndon_boundaries, proportional)
+               --factorization <FACT>     (default: icfl, or: cfl, icfl_cfl, cfl_icfl)
+  generate   Generate a random DNA FASTA file
+               --length <NUMBER>  (required)
+  plot       Plot timings already logged by \"suite --log-execution\"
+               --chunk-sizes <CHUNK SIZES>  (required)
+               --max-duration <MICROS>      (default: 1_000_000)
+               --classic-micros <MICROS>    (default: 0)
+  verify     Check the Innovative Suffix Array in linear time, without a reference one
+               --chunk-sizes <CHUNK SIZES>  (default: none)
+               --factorization <FACT>       (default: icfl, or: cfl, icfl_cfl, cfl_icfl)
+               --split-policy <POLICY>      (default: remainder_first, or: remainder_last, balanced,
+                                             lyndon_boundaries, proportional)
+               --sa-file <PATH>             (check a Suffix Array file instead, like \"build\" ones)
+               --rules-oracle               (check every Rules decision, against the string comparison)
+
+CHUNK SIZES: comma-separated \"none\", \"N\", \"A..B\" (B included) or \"A..B:STEP\" (B excluded).
+  For example: \"2..9,10..100:10,none\"
+
+FASTA RECORDS: \"build\", \"suite\", \"factorize\" and \"verify\" never read header lines as the string.
+  --records <SELECTION>  (default: 0, or: \"N\" from 0, \"id:ID\", \"all\", \"all:SEPARATOR\")
+  --uppercase            (turn the string to upper case)";
+
+const VALUE_OPTIONS: [&str; 24] = [
+    "config",
+    "chunk-size",
+    "chunk-sizes",
+    "output",
+    "max-duration",
+    "attempts",
+    "length",
+    "classic-micros",
+    "warm-up",
+    "outlier-iqr",
+    "plot-statistic",
+    "baselines",
+    "sa-file",
+    "threads",
+    "tree-threads",
+    "tree-layout",
+    "tree-layouts",
+    "factorization",
+    "factorizations",
+    "chunk-threshold",
+    "auto-chunk-size",
+    "split-policy",
+    "split-policies",
+    "records",
+];
+
+const FLAG_OPTIONS: [&str; 6] = [
+    "log-execution",
+    "log-fact",
+    "log-trees",
+    "no-plot",
+    "rules-oracle",
+    "uppercase",
+];
+
+pub fn run_cli(args: &[String]) -> i32 {
+    // Returns the process exit code.
+    let Some(command) = args.first() else {
+        eprintln!("{}", USAGE);
+        return 1;
+    };
+    let result = Args::parse(&args[1..], &VALUE_OPTIONS, &FLAG_OPTIONS).and_then(|args| {
+        match command.as_str() {
+            "build" => command_build(&args),
+            "suite" => command_suite(&args),
+            "factorize" => command_factorize(&args),
+            "generate" => command_generate(&args),
+            "plot" => command_plot(&args),
+            "verify" => command_verify(&args),
+            "help" | "--help" | "-h" => {
+                println!("{}", USAGE);
+                Ok(true)
+            }
+            _ => Err(format!("Unknown command \"{}\"", command)),
+        }
+    });
+    match result {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(message) => {
+            eprintln!("ERROR: {}\n\n{}", message, USAGE);
+            1
+        }
+    }
+}
+
+fn get_single_chunk_size(args: &Args) -> Result<Option<usize>, String> {
+    let chunk_sizes = parse_chunk_sizes(args.get_option("chunk-size").unwrap_or("none"))?;
+    if chunk_sizes.len() != 1 {
+        return Err("Expected only one chunk size".to_string());
+    }
+    Ok(chunk_sizes[0])
+}
+fn get_chunk_threshold(args: &Args) -> Result<Option<usize>, String> {
+    match args.get_option("chunk-threshold") {
+        Some(_) => Ok(Some(args.get_option_parsed("chunk-threshold", 0)?)),
+        None => Ok(None),
+    }
+}
+fn get_split_policy(args: &Args) -> Result<SplitPolicy, String> {
+    match args.get_option("split-policy") {
+        Some(split_policy) => SplitPolicy::parse(split_policy),
+        None => Ok(SplitPolicy::RemainderFirst),
+    }
+}
+fn get_auto_chunk_size(args: &Args) -> Result<Option<AutoChunkSize>, String> {
+    match args.get_option("auto-chunk-size") {
+        Some(auto_chunk_size) => Ok(Some(AutoChunkSize::parse(auto_chunk_size)?)),
+        None => Ok(None),
+    }
+}
+fn get_fasta_selection(args: &Args, default: FastaSelection) -> Result<FastaSelection, String> {
+    let mut selection = default;
+    if let Some(records) = args.get_option("records") {
+        selection.records = FastaRecordSelection::parse(records)?;
+    }
+    if args.has_flag("uppercase") {
+        selection.uppercase = true;
+    }
+    Ok(selection)
+}
+fn get_factorization(args: &Args) -> Result<FactorizationStrategy, String> {
+    match args.get_option("factorization") {
+        Some(factorization) => FactorizationStrategy::parse(factorization),
+        None => Ok(FactorizationStrategy::Icfl),
+    }
+}
+fn get_chunk_sizes(args: &Args, default: Option<&str>) -> Result<Vec<Option<usize>>, String> {
+    match args.get_option("chunk-sizes").or(default) {
+        Some(expression) => parse_chunk_sizes(expression),
+        None => Err("Missing \"--chunk-sizes\"".to_string()),
+    }
+}
+
+fn command_build(args: &Args) -> Result<bool, String> {
+    let fasta_file_name = args.get_positional(0, "FASTA FILE NAME")?;
+    let chunk_size = get_single_chunk_size(args)?;
+    let log_execution = args.has_flag("log-execution");
+
+    let str = get_fasta_content(
+        get_path_in_generated_folder(fasta_file_name),
+        &get_fasta_selection(args, FastaSelection::default())?,
+    );
+    let mut logger = ResultsFolderLogger::new(
+        DEFAULT_RESULTS_FOLDER,
+        fasta_file_name,
+        log_execution,
+        false,
+        false,
+    );
+    let results = InnovativeSuffixArrayBuilder::new(str.as_bytes())
+        .chunk_size(chunk_size)
+        .chunk_threshold(get_chunk_threshold(args)?)
+        .split_policy(get_split_policy(args)?)
+        .auto_chunk_size(get_auto_chunk_size(args)?)
+        .factorization(get_factorization(args)?)
+        .num_threads(args.get_option_parsed("threads", 1)?)
+        .tree_num_threads(args.get_option_parsed("tree-threads", 1)?)
+        .tree_layout(match args.get_option("tree-layout") {
+            Some(tree_layout) => TreeLayout::parse(tree_layout)?,
+            None => TreeLayout::Nested,
+        })
+        .rules_oracle(args.has_flag("rules-oracle"))
+        .reset_peak_rss(true)
+        .logger(&mut logger)
+        .compute();
+
+    let output = match args.get_option("output") {
+        Some(output) => output.to_string(),
+        None => {
+            make_sure_directory_exist(get_path_for_project_folder(
+                DEFAULT_RESULTS_FOLDER,
+                fasta_file_name,
+            ));
+            get_path_for_project_suffix_array_file(
+                DEFAULT_RESULTS_FOLDER,
+                fasta_file_name,
+                results.chunk_size.unwrap_or(0),
+            )
+        }
+    };
+    log_suffix_array(&results.suffix_array, output.clone());
+
+    if let Some(auto_chunk_size) = get_auto_chunk_size(args)? {
+        println!(
+            "Chunk Size ({}): {}",
+            auto_chunk_size.get_label(),
+            results
+                .chunk_size
+                .map_or("none".to_string(), |chunk_size| chunk_size.to_string())
+        );
+    }
+
+    let et = &results.execution_info.execution_timing;
+    print_duration(
+        " > Phase 1: Factorization ",
+        et.p1_fact.dur.as_micros() as u64,
+    );
+    print_duration(
+        " > Phase 2: Prefix Tree   ",
+        et.p2_tree.dur.as_micros() as u64,
+    );
+    if let Some(p2_tree_bucketing) = et.p2_tree_bucketing {
+        print_duration(
+            "   > Bucketing            ",
+            p2_tree_bucketing.as_micros() as u64,
+        );
+    }
+    print_duration(
+        " > Phase 3: Suffix Array  ",
+        et.p3_sa.dur.as_micros() as u64,
+    );
+    println!("Suffix Array written in \"{}\"", output);
+    if let Some(rules_oracle) = &results.execution_info.rules_oracle {
+        print_rules_oracle_report(rules_oracle);
+        return Ok(rules_oracle.mismatches.is_empty());
+    }
+    Ok(true)
+}
+
+fn print_rules_oracle_report(rules_oracle: &RulesOracleReport) {
+    println!(
+        "Rules Oracle: {} checks, {} mismatches",
+        rules_oracle.num_checks,
+        rules_oracle.mismatches.len()
+    );
+    // Mismatches by Branch, in order of first occurrence.
+    let mut branch_and_count_list = Vec::new();
+    for rules_mismatch in &rules_oracle.mismatches {
+        match branch_and_count_list
+            .iter_mut()
+            .find(|(branch, _)| *branch == rules_mismatch.branch)
+        {
+            Some((_, count)) => *count += 1,
+            None => branch_and_count_list.push((rules_mismatch.branch, 1)),
+        }
+    }
+    for (branch, count) in branch_and_count_list {
+        println!(" > {:27}: {}", branch.get_label(), count);
+    }
+}
+
+fn command_suite(args: &Args) -> Result<bool, String> {
+    let mut config = match args.get_option("config") {
+        Some(config_path) => SuiteConfig::from_file(config_path.to_string())?,
+        None => {
+            let fasta_file_name = args.get_positional(0, "FASTA FILE NAME")?;
+            let mut config = SuiteConfig::new(fasta_file_name, get_chunk_sizes(args, None)?);
+            config.max_duration_in_micros = args.get_option_parsed("max-duration", 1_000_000)?;
+            if let Some(baselines) = args.get_option("baselines") {
+                config.baselines = BaselineSaca::parse_list(baselines)?;
+            }
+            config.num_attempts = args.get_option_parsed("attempts", 1)?;
+            config.num_warm_up_attempts = args.get_option_parsed("warm-up", 0)?;
+            config.num_threads = args.get_option_parsed::<usize>("threads", 1)?.max(1);
+            config.tree_num_threads = args.get_option_parsed::<usize>("tree-threads", 1)?.max(1);
+            if let Some(tree_layouts) = args.get_option("tree-layouts") {
+                config.tree_layouts = TreeLayout::parse_list(tree_layouts)?;
+            }
+            config.chunk_threshold = get_chunk_threshold(args)?;
+            config.auto_chunk_size = get_auto_chunk_size(args)?;
+            if let Some(split_policies) = args.get_option("split-policies") {
+                config.split_policies = SplitPolicy::parse_list(split_policies)?;
+            }
+            if let Some(factorizations) = args.get_option("factorizations") {
+                config.factorizations = FactorizationStrategy::parse_list(factorizations)?;
+            }
+            if let Some(outlier_iqr_factor) = args.get_option("outlier-iqr") {
+                config.outlier_iqr_factor = Some(outlier_iqr_factor.parse().map_err(|_| {
+                    format!(
+                        "Invalid value \"{}\" for \"--outlier-iqr\"",
+                        outlier_iqr_factor
+                    )
+                })?);
+            }
+            if let Some(plot_statistic) = args.get_option("plot-statistic") {
+                config.plot_statistic = PlotStatistic::parse(plot_statistic)?;
+            }
+            config.log_execution = args.has_flag("log-execution");
+            config.log_fact = args.has_flag("log-fact");
+            config.log_trees_and_suffix_array = args.has_flag("log-trees");
+            config.draw_plot = !args.has_flag("no-plot");
+            config
+        }
+    };
+    // Also with "--config": the options win over the file.
+    config.fasta_selection = get_fasta_selection(args, config.fasta_selection)?;
+    full_suite(&config);
+    Ok(true)
+}
+
+fn command_factorize(args: &Args) -> Result<bool, String> {
+    let fasta_file_name = args.get_positional(0, "FASTA FILE NAME")?;
+    let chunk_size = get_single_chunk_size(args)?;
+    let factorization = get_factorization(args)?;
+
+    let str = get_fasta_content(
+        get_path_in_generated_folder(fasta_file_name),
+        &get_fasta_selection(args, FastaSelection::default())?,
+    );
+    let str = str.as_bytes();
+    let chunking = Chunking {
+        chunk_size,
+        threshold: get_chunk_threshold(args)?,
+        split_policy: get_split_policy(args)?,
+    };
+    let factorization = factorization.get_factorizer(chunking).factorize(str);
+    let factorization_stats = compute_factorization_stats(&factorization, str.len());
+    let icfl_indexes = factorization.icfl_indexes;
+    let factor_indexes = factorization.factor_indexes;
+
+    make_sure_directory_exist(get_path_for_project_folder(
+        DEFAULT_RESULTS_FOLDER,
+        fasta_file_name,
+    ));
+    let filepath = get_path_for_project_factorization_file(
+        DEFAULT_RESULTS_FOLDER,
+        fasta_file_name,
+        chunk_size.unwrap_or(0),
+    );
+    log_factorization(&factor_indexes, &icfl_indexes, str, filepath.clone());
+    let stats_filepath = get_path_for_project_factorization_stats_file_json(
+        DEFAULT_RESULTS_FOLDER,
+        fasta_file_name,
+        chunk_size.unwrap_or(0),
+    );
+    dump_json_in_file(&factorization_stats, stats_filepath.clone());
+    println!(
+        "{} ICFL Factors, {} Factors: written in \"{}\"",
+        icfl_indexes.len(),
+        factor_indexes.len(),
+        filepath
+    );
+    println!(
+        "{} Custom Factors, max size {}, {:.1}% Custom positions: statistics written in \"{}\"",
+        factorization_stats.num_custom_factors,
+        factorization_stats.max_factor_size,
+        factorization_stats.custom_positions_fraction * 100.0,
+        stats_filepath
+    );
+    Ok(true)
+}
+
+fn command_generate(args: &Args) -> Result<bool, String> {
+    let fasta_file_name = args.get_positional(0, "FASTA FILE NAME")?;
+    let length = args.get_option_parsed("length", 0)?;
+    if length == 0 {
+        return Err("Missing \"--length\"".to_string());
+    }
+    main_generation(fasta_file_name, length);
+    Ok(true)
+}
+
+fn command_plot(args: &Args) -> Result<bool, String> {
+    let fasta_file_name = args.get_positional(0, "FASTA FILE NAME")?;
+    let chunk_size_vec = get_chunk_sizes(args, None)?;
+    let mut chunk_size_and_phase_micros_list = Vec::new();
+    for chunk_size in chunk_size_vec {
+        let chunk_size_or_zero = chunk_size.unwrap_or(0);
+        let execution_timing_file_format: ExecutionInfoFileFormat =
+            read_json_from_file(get_path_for_project_timing_file_json(
+                DEFAULT_RESULTS_FOLDER,
+                fasta_file_name,
+                chunk_size_or_zero,
+            ));
+        chunk_size_and_phase_micros_list.push((
+            chunk_size_or_zero,
+            execution_timing_file_format.get_phases_micros(),
+        ));
+    }
+    draw_plot_from_monitor(
+        DEFAULT_PLOTS_FOLDER,
+        fasta_file_name,
+        args.get_option_parsed("classic-micros", 0)?,
+        chunk_size_and_phase_micros_list,
+        args.get_option_parsed("max-duration", 1_000_000)?,
+    );
+    Ok(true)
+}
+
+fn command_verify(args: &Args) -> Result<bool, String> {
+    let fasta_file_name = args.get_positional(0, "FASTA FILE NAME")?;
+    let str = get_fasta_content(
+        get_path_in_generated_folder(fasta_file_name),
+        &get_fasta_selection(args, FastaSelection::default())?,
+    );
+
+    // Suffix Array File, as written by "build" or "suite --log-trees".
+    if let Some(sa_file) = args.get_option("sa-file") {
+        let suffix_array = read_suffix_array(sa_file.to_string())?;
+        let report = verify_suffix_array(str.as_bytes(), &suffix_array);
+        println!("[FILE={sa_file}] {report}");
+        return Ok(report.is_ok());
+    }
+
+    let chunk_size_vec = get_chunk_sizes(args, Some("none"))?;
+    let factorization = get_factorization(args)?;
+    let split_policy = get_split_policy(args)?;
+    let rules_oracle = args.has_flag("rules-oracle");
+    let mut success = true;
+    for chunk_size in chunk_size_vec {
+        let results = InnovativeSuffixArrayBuilder::new(str.as_bytes())
+            .chunk_size(chunk_size)
+            .factorization(factorization)
+            .split_policy(split_policy)
+            .rules_oracle(rules_oracle)
+            .compute();
+        let chunk_size_or_zero = chunk_size.unwrap_or(0);
+        let report = verify_suffix_array(str.as_bytes(), &results.suffix_array);
+        if report.is_ok() {
+            println!("[CHUNK SIZE={chunk_size_or_zero}] OK");
+        } else {
+            println!("[CHUNK SIZE={chunk_size_or_zero}] Computed {report} :(");
+            success = false;
+        }
+        if let Some(rules_oracle) = &results.execution_info.rules_oracle {
+            print_rules_oracle_report(rules_oracle);
+            success &= rules_oracle.mismatches.is_empty();
+        }
+    }
+    Ok(success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        Args::parse(&args, &VALUE_OPTIONS, &FLAG_OPTIONS)
+    }
+
+    #[test]
+    fn fasta_selection_options() {
+        let args = parse(&["000"]).unwrap();
+        assert_eq!(
+            get_fasta_selection(&args, FastaSelection::default()),
+            Ok(FastaSelection::default())
+        );
+        let args = parse(&["000", "--records", "all:$", "--uppercase"]).unwrap();
+        assert_eq!(
+            get_fasta_selection(&args, FastaSelection::default()),
+            Ok(FastaSelection::new(FastaRecordSelection::Concatenate(Some('$'))).uppercase(true))
+        );
+        // Options win over the suite config ones.
+        let args = parse(&["000", "--records", "id:chr2"]).unwrap();
+        assert_eq!(
+            get_fasta_selection(
+                &args,
+                FastaSelection::new(FastaRecordSelection::RecordAt(1)).uppercase(true)
+            ),
+            Ok(
+                FastaSelection::new(FastaRecordSelection::RecordWithId("chr2".to_string()))
+                    .uppercase(true)
+            )
+        );
+        let args = parse(&["000", "--records", "first"]).unwrap();
+        assert!(get_fasta_selection(&args, FastaSelection::default()).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod args;
+pub mod chunk_sizes;
+pub mod commands;
@@ -14,7 +14,7 @@ pub fn jaccard_similarity_via_kmers(src1: &str, src2: &str, k: usize) -> f32 {
 }
 
 /*
-fn count_shared_items(vec1: &Vec<&str>, vec2: &Vec<&str>) -> usize {
+fn count_shared_items(vec1: &[&str], vec2: &[&str]) -> usize {
     // Sorted before calling this? We'll see...
     let mut result = 0;
     for item1 in vec1 {
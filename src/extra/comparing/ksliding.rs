@@ -3,7 +3,7 @@ use std::collections::HashSet;
 // pub type KmersSet<'a> = BTreeSet<&'a str>;
 pub type KmersSet<'a> = HashSet<&'a str>;
 
-pub fn get_kmers(src: &str, k: usize) -> KmersSet {
+pub fn get_kmers(src: &str, k: usize) -> KmersSet<'_> {
     let mut kmers = KmersSet::new();
     let mut offset = 0;
     while offset + k <= src.len() {
@@ -1,11 +1,9 @@
 use crate::files::fasta::save_fasta_with_content;
-use rand::prelude::*;
+use crate::files::paths::get_path_in_generated_folder;
 
 const GENETIC_ALPHABET: [char; 4] = ['A', 'C', 'G', 'T'];
 
-pub fn main_generation() {
-    let mut num_length = 700_000;
-
+pub fn main_generation(fasta_file_name: &str, mut num_length: usize) {
     let max_chars_per_iteration = 100_000;
     let num_lines_max = num_length / max_chars_per_iteration;
 
@@ -13,7 +11,7 @@ pub fn main_generation() {
 
     println!("Generating file with {} chars", num_length);
     while num_length > 0 {
-        let num_chars_this_iteration = i32::min(max_chars_per_iteration, num_length);
+        let num_chars_this_iteration = usize::min(max_chars_per_iteration, num_length);
         let mut i = 0;
         while i < num_chars_this_iteration {
             let rand_index = rand::random_range(0..GENETIC_ALPHABET.len());
@@ -31,8 +29,8 @@ pub fn main_generation() {
     }
     println!("Generated, now it's time to save...");
 
-    let filepath = "generated/002_700.fasta";
-    save_fasta_with_content(filepath.into(), output);
+    let filepath = get_path_in_generated_folder(fasta_file_name);
+    save_fasta_with_content(filepath, output);
 
     println!("OK!");
 }
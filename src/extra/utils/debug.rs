@@ -1,4 +1,4 @@
-pub fn print_vec(unique_slides1: &Vec<&str>) {
+pub fn print_vec(unique_slides1: &[&str]) {
     // For debugging purposes.
     for x in unique_slides1 {
         print!("{}, ", x);
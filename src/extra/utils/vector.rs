@@ -16,12 +16,11 @@ pub fn vec_skip_duplicates(vec: Vec<&str>) -> Vec<&str> {
     result
 }
 
-pub fn print_array_of_numbers(list: &Vec<usize>) -> String {
+pub fn print_array_of_numbers(list: &[usize]) -> String {
     let mut result = String::from("[");
 
     let last_item = list[list.len() - 1];
-    for i in 0..list.len() - 1 {
-        let curr_item = list[i];
+    for curr_item in &list[..list.len() - 1] {
         result.push_str(&format!("{}, ", curr_item));
     }
     result.push_str(&format!("{}]", last_item));
@@ -0,0 +1,257 @@
+use crate::factorization::get_max_factor_size;
+use crate::prefix_tree::symbol::Symbol;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+// AUTOMATIC CHUNK SIZE
+// * "Heuristic": from the alphabet size and the Factor length distribution only;
+// * "DryRun": the Chunk Sizes around the heuristic one are tried on a sample of the string, the
+//   one with the fastest Phases 2 and 3 is chosen.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoChunkSize {
+    Heuristic,
+    DryRun,
+}
+impl AutoChunkSize {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "heuristic" => Ok(AutoChunkSize::Heuristic),
+            "dry_run" => Ok(AutoChunkSize::DryRun),
+            _ => Err(format!(
+                "Invalid auto chunk size \"{}\", expected \"heuristic\" or \"dry_run\"",
+                value
+            )),
+        }
+    }
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            AutoChunkSize::Heuristic => "heuristic",
+            AutoChunkSize::DryRun => "dry_run",
+        }
+    }
+}
+
+const MIN_CHUNK_SIZE: usize = 2;
+const DRY_RUN_MIN_SAMPLE_SIZE: usize = 1 << 10;
+const DRY_RUN_MAX_SAMPLE_SIZE: usize = 1 << 14;
+const DRY_RUN_SAMPLE_FRACTION: usize = 16; // The sample is at most "1/16" of the string.
+const DRY_RUN_CANDIDATES_DISTANCE: usize = 2; // Tried: heuristic one, plus or minus this.
+const DRY_RUN_ATTEMPTS: usize = 3; // The fastest one is kept, to limit the noise.
+const DRY_RUN_MIN_GAIN: f64 = 0.1; // Against the heuristic one, to be preferred.
+
+pub fn select_chunk_size<T: Symbol>(
+    auto_chunk_size: AutoChunkSize,
+    str: &[T],
+    factor_indexes: &[usize], // Of the Factorization without chunks.
+    measure_phases_2_and_3: impl FnMut(&[T], Option<usize>) -> Duration,
+) -> Option<usize> {
+    let chunk_size = select_chunk_size_by_heuristic(str, factor_indexes);
+    match auto_chunk_size {
+        AutoChunkSize::Heuristic => chunk_size,
+        AutoChunkSize::DryRun => {
+            select_chunk_size_by_dry_run(str, chunk_size, measure_phases_2_and_3)
+        }
+    }
+}
+
+pub fn select_chunk_size_by_heuristic<T: Symbol>(
+    str: &[T],
+    factor_indexes: &[usize],
+) -> Option<usize> {
+    if str.is_empty() {
+        return None;
+    }
+    let chunk_size = get_chunk_size_by_length(str.len(), get_alphabet_size(str));
+
+    // Factors not longer than the Chunk Size are not split: if there are only those, chunking
+    // would change nothing.
+    if get_max_factor_size(factor_indexes, str.len()) <= chunk_size {
+        None
+    } else {
+        Some(chunk_size)
+    }
+}
+
+fn get_chunk_size_by_length(str_length: usize, alphabet_size: usize) -> usize {
+    // Local Suffixes longer than "log_σ(n)" are mostly unique: chunking a bit below that length
+    // keeps the Tree shallow without piling up Custom LSs in the same Nodes (sorted comparing
+    // Global Suffixes). Empirically, on DNA the fastest Chunk Size is about "log_σ(n) - 2".
+    let log_n = (str_length as f64).ln() / (alphabet_size.max(2) as f64).ln();
+    (log_n.floor() as usize)
+        .saturating_sub(2)
+        .max(MIN_CHUNK_SIZE)
+}
+
+fn get_alphabet_size<T: Symbol>(str: &[T]) -> usize {
+    str.iter().collect::<BTreeSet<_>>().len()
+}
+
+fn select_chunk_size_by_dry_run<T: Symbol>(
+    str: &[T],
+    heuristic_chunk_size: Option<usize>,
+    mut measure_phases_2_and_3: impl FnMut(&[T], Option<usize>) -> Duration,
+) -> Option<usize> {
+    // The fastest Chunk Size depends on the length: the sample only tells how far the heuristic
+    // is from the fastest one, then the same distance is applied to the whole string.
+    let heuristic_chunk_size = heuristic_chunk_size?;
+    let sample_size = (str.len() / DRY_RUN_SAMPLE_FRACTION).min(DRY_RUN_MAX_SAMPLE_SIZE);
+    if sample_size < DRY_RUN_MIN_SAMPLE_SIZE {
+        // Too short to measure anything.
+        return Some(heuristic_chunk_size);
+    }
+    // Sample from the middle of the string, the start could be not representative.
+    let sample_start = (str.len() - sample_size) / 2;
+    let sample = &str[sample_start..sample_start + sample_size];
+    let sample_heuristic_chunk_size =
+        get_chunk_size_by_length(sample.len(), get_alphabet_size(sample));
+
+    let min_candidate = sample_heuristic_chunk_size
+        .saturating_sub(DRY_RUN_CANDIDATES_DISTANCE)
+        .max(MIN_CHUNK_SIZE);
+    let max_candidate = sample_heuristic_chunk_size + DRY_RUN_CANDIDATES_DISTANCE;
+    let mut durations = Vec::new();
+    for candidate in min_candidate..=max_candidate {
+        let duration = (0..DRY_RUN_ATTEMPTS)
+            .map(|_| measure_phases_2_and_3(sample, Some(candidate)))
+            .min()
+            .unwrap();
+        durations.push((duration, candidate));
+    }
+    let heuristic_duration = durations
+        .iter()
+        .find(|&&(_, candidate)| candidate == sample_heuristic_chunk_size)
+        .unwrap()
+        .0;
+    let mut best = *durations.iter().min().unwrap();
+    if best.0.as_secs_f64() > heuristic_duration.as_secs_f64() * (1.0 - DRY_RUN_MIN_GAIN) {
+        best = (heuristic_duration, sample_heuristic_chunk_size);
+    }
+    Some(
+        (heuristic_chunk_size + best.1)
+            .saturating_sub(sample_heuristic_chunk_size)
+            .max(MIN_CHUNK_SIZE),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factorization::custom_factorization::Chunking;
+    use crate::factorization::factorizer::FactorizationStrategy;
+    use crate::test_utils::{for_each_random_string, get_repetitive_strings};
+    use crate::InnovativeSuffixArrayBuilder;
+
+    fn get_factor_indexes(str: &[u8]) -> Vec<usize> {
+        FactorizationStrategy::Icfl
+            .get_factorizer(Chunking::new(None))
+            .factorize(str)
+            .factor_indexes
+    }
+    fn assert_valid_heuristic_chunk_size(str: &[u8]) {
+        // A Chunk Size is only selected if it actually splits some Factor.
+        let factor_indexes = get_factor_indexes(str);
+        if let Some(chunk_size) = select_chunk_size_by_heuristic(str, &factor_indexes) {
+            assert!(
+                MIN_CHUNK_SIZE <= chunk_size
+                    && chunk_size < get_max_factor_size(&factor_indexes, str.len()),
+                "str={:?}, chunk_size={}",
+                String::from_utf8_lossy(str),
+                chunk_size
+            );
+        }
+    }
+
+    #[test]
+    fn heuristic_chunk_size_is_valid() {
+        assert_eq!(select_chunk_size_by_heuristic::<u8>(b"", &[]), None);
+        assert_eq!(select_chunk_size_by_heuristic(b"A", &[0]), None);
+        for_each_random_string(22, 500, 1..=200, 1..=4, |str, _| {
+            assert_valid_heuristic_chunk_size(str);
+        });
+        for str in get_repetitive_strings(60) {
+            assert_valid_heuristic_chunk_size(&str);
+        }
+        // One long Factor: always chunked.
+        let str = b"DCBA".repeat(1000);
+        assert_eq!(
+            select_chunk_size_by_heuristic(&str, &get_factor_indexes(&str)),
+            Some(get_chunk_size_by_length(str.len(), 4))
+        );
+    }
+
+    #[test]
+    fn dry_run_chunk_size_stays_around_heuristic_one() {
+        // DNA long enough for a sample: "log_4(20000) - 2 = 5" on the string, and candidates
+        // from 2 to 5 around "log_4(1250) - 2 = 3" on the sample.
+        for_each_random_string(22, 1, 20_000..=20_000, 4..=4, |str, _| {
+            let factor_indexes = get_factor_indexes(str);
+            let heuristic_chunk_size = select_chunk_size_by_heuristic(str, &factor_indexes);
+            assert_eq!(heuristic_chunk_size, Some(5));
+            let select = |fastest: usize| {
+                let mut candidates = Vec::new();
+                let chunk_size =
+                    select_chunk_size(AutoChunkSize::DryRun, str, &factor_indexes, |sample, c| {
+                        assert_eq!(sample.len(), str.len() / DRY_RUN_SAMPLE_FRACTION);
+                        candidates.push(c.unwrap());
+                        // Far from the fastest one is slower.
+                        Duration::from_micros(100 + 100 * c.unwrap().abs_diff(fastest) as u64)
+                    });
+                candidates.dedup();
+                // "3 - 2" would be below the minimum Chunk Size.
+                assert_eq!(candidates, vec![MIN_CHUNK_SIZE, 3, 4, 5]);
+                chunk_size
+            };
+            assert_eq!(select(3), Some(5));
+            assert_eq!(select(5), Some(7));
+            assert_eq!(select(2), Some(4));
+            // The fastest of the candidates, even if a smaller one would be faster.
+            assert_eq!(select(0), Some(4));
+        });
+    }
+
+    #[test]
+    fn dry_run_keeps_heuristic_one_without_enough_gain() {
+        for_each_random_string(22, 1, 20_000..=20_000, 4..=4, |str, _| {
+            let factor_indexes = get_factor_indexes(str);
+            let chunk_size =
+                select_chunk_size(AutoChunkSize::DryRun, str, &factor_indexes, |_, c| {
+                    // The fastest one, but less than "DRY_RUN_MIN_GAIN" faster.
+                    Duration::from_micros(if c == Some(2) { 95 } else { 100 })
+                });
+            assert_eq!(chunk_size, Some(5));
+        });
+    }
+
+    #[test]
+    fn dry_run_on_tiny_strings_is_heuristic() {
+        // Too short for a sample: nothing is measured.
+        for_each_random_string(22, 200, 0..=100, 1..=4, |str, _| {
+            let factor_indexes = get_factor_indexes(str);
+            let chunk_size =
+                select_chunk_size(AutoChunkSize::DryRun, str, &factor_indexes, |_, _| {
+                    panic!("Measured a sample of a tiny string")
+                });
+            assert_eq!(
+                chunk_size,
+                select_chunk_size_by_heuristic(str, &factor_indexes)
+            );
+        });
+    }
+
+    #[test]
+    fn builder_with_auto_chunk_size_on_tiny_strings() {
+        for_each_random_string(22, 100, 0..=20, 1..=4, |str, _| {
+            let mut naive_suffix_array = (0..str.len()).collect::<Vec<_>>();
+            naive_suffix_array.sort_by_key(|&gs_index| &str[gs_index..]);
+            for auto_chunk_size in [AutoChunkSize::Heuristic, AutoChunkSize::DryRun] {
+                let results = InnovativeSuffixArrayBuilder::new(str)
+                    .auto_chunk_size(Some(auto_chunk_size))
+                    .compute();
+                assert!(results.chunk_size.is_none_or(|c| c >= MIN_CHUNK_SIZE));
+                assert_eq!(results.suffix_array, naive_suffix_array);
+            }
+        });
+    }
+}
@@ -3,8 +3,7 @@ pub fn cfl(s: &str) -> Vec<String> {
     let factors = cfl_duval(s.as_bytes());
 
     let mut result = Vec::new();
-    for i in 0..factors.len() {
-        let factor_bytes = factors[i];
+    for factor_bytes in factors {
         let factor = String::from_utf8(factor_bytes.to_vec()).unwrap();
         result.push(factor);
     }
@@ -12,7 +11,17 @@ pub fn cfl(s: &str) -> Vec<String> {
     result
 }
 
-pub fn cfl_duval(s: &[u8]) -> Vec<&[u8]> {
+pub fn get_cfl_indexes<T: Ord>(str: &[T]) -> Vec<usize> {
+    let mut result = Vec::new();
+    let mut i = 0;
+    for factor in cfl_duval(str) {
+        result.push(i);
+        i += factor.len();
+    }
+    result
+}
+
+pub fn cfl_duval<T: Ord>(s: &[T]) -> Vec<&[T]> {
     let mut res = Vec::new();
     let n = s.len();
     let mut i = 0;
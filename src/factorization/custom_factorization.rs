@@ -1,101 +1,404 @@
-pub fn get_custom_factors_and_more_using_chunk_size(
-    icfl_indexes: &Vec<usize>,
-    chunk_size: Option<usize>,
-    str_length: usize,
-) -> (Vec<usize>, Vec<bool>, Vec<usize>) {
-    // From string "AAA|B|CAABCA|DCAABCA"
-    //              ^   ^ ^      ^
-    //        ICFL=[0,  3,4,     10]
-    //  str_length = 17
-    //  chunk_size = 3
-    // Idx. 2 Is Custom  = [Char Index] => True if it's in the
-    //                                     Last Custom Factor of
-    //                                     an ICFL Factor.
-    // Idx. 2 ICFL Fact. = [Char Index] => ICFL Factor Index of it
-    // Returns:
-    //   factor_indexes = [0, 3, 4, 7, 10, 11, 14]
-    //                        (A,A,A,B,C,A,A,B,C,A,D,C,A,A,B,C,A)
-    //   idx_to_is_custom   = [0,0,0,0,1,1,1,0,0,0,1,1,1,1,0,0,0]
-    //   idx_to_icfl_factor = [0,0,0,1,2,2,2,2,2,2,3,3,3,3,3,3,3]
-    let mut factor_indexes = Vec::new();
-    let mut idx_to_is_custom = Vec::with_capacity(str_length);
-    let mut idx_to_icfl_factor = Vec::with_capacity(str_length);
+use crate::factorization::cfl::get_cfl_indexes;
+use serde::{Deserialize, Serialize};
 
-    if let Some(chunk_size) = chunk_size {
-        for i in 0..icfl_indexes.len() {
-            let curr_icfl_factor_index = icfl_indexes[i];
+// CHUNKING
+// How Factors are split in Custom Factors: with a Threshold, only Factors longer than it are split.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chunking {
+    pub chunk_size: Option<usize>,
+    pub threshold: Option<usize>,
+    pub split_policy: SplitPolicy,
+}
+impl Chunking {
+    pub fn new(chunk_size: Option<usize>) -> Self {
+        check_chunk_size(chunk_size);
+        Self {
+            chunk_size,
+            threshold: None,
+            split_policy: SplitPolicy::RemainderFirst,
+        }
+    }
+    pub fn can_split(&self, factor_size: usize) -> bool {
+        match self.chunk_size {
+            Some(chunk_size) => {
+                factor_size >= chunk_size
+                    && self
+                        .threshold
+                        .is_none_or(|threshold| factor_size > threshold)
+            }
+            None => false,
+        }
+    }
+}
 
-            // Curr ICFL Factor Size
-            let curr_icfl_factor_size = if i < icfl_indexes.len() - 1 {
-                icfl_indexes[i + 1]
-            } else {
-                str_length
-            } - curr_icfl_factor_index;
+pub fn check_chunk_size(chunk_size: Option<usize>) {
+    // A Chunk Size of zero would divide by zero while splitting Factors.
+    assert_ne!(chunk_size, Some(0), "Chunk size can't be zero");
+}
 
-            // Updating "factor_indexes"
-            if curr_icfl_factor_size < chunk_size {
-                // ICFL Factor can't be split.
-                // For example: ICFL Factor "B".
-                factor_indexes.push(curr_icfl_factor_index);
-            } else {
-                // ICFL Factor can be split.
-                // For example: ICFL Factor "D|CAA|BCA".
-                let smaller_cf_size = curr_icfl_factor_size % chunk_size;
-                if smaller_cf_size > 0 {
-                    // Here the first Custom Factor is smaller.
-                    // For example: "D" of "D|CAA|BCA".
-                    factor_indexes.push(curr_icfl_factor_index);
-                }
-                let mut curr_cf_idx = curr_icfl_factor_index + smaller_cf_size;
-                while curr_cf_idx < curr_icfl_factor_index + curr_icfl_factor_size {
-                    // Here all Custom Factor of size Chunk Size.
-                    factor_indexes.push(curr_cf_idx);
-                    curr_cf_idx += chunk_size;
+// SPLIT POLICIES
+// Where a Factor is split, with Chunk Size "c":
+// * "RemainderFirst": the smaller chunk first, then chunks of "c" (as it always was);
+// * "RemainderLast": chunks of "c", then the smaller one;
+// * "Balanced": as few chunks as "RemainderFirst", with sizes differing by one at most;
+// * "LyndonBoundaries": only between its Lyndon Factors, packed in chunks up to "c" (Lyndon
+//   Factors longer than "c" are split as "RemainderFirst");
+// * "Proportional": as "RemainderFirst", with "c" scaled by the Factor size over the mean one.
+// The last chunk of each ICFL Factor is the only one not Custom, whatever the policy.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitPolicy {
+    RemainderFirst,
+    RemainderLast,
+    Balanced,
+    LyndonBoundaries,
+    Proportional,
+}
+impl SplitPolicy {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "remainder_first" => Ok(SplitPolicy::RemainderFirst),
+            "remainder_last" => Ok(SplitPolicy::RemainderLast),
+            "balanced" => Ok(SplitPolicy::Balanced),
+            "lyndon_boundaries" => Ok(SplitPolicy::LyndonBoundaries),
+            "proportional" => Ok(SplitPolicy::Proportional),
+            _ => Err(format!(
+                "Invalid split policy \"{}\", expected \"remainder_first\", \"remainder_last\", \"balanced\", \"lyndon_boundaries\" or \"proportional\"",
+                value
+            )),
+        }
+    }
+    pub fn parse_list(expression: &str) -> Result<Vec<Self>, String> {
+        // Comma-separated, like "remainder_first,balanced".
+        expression
+            .split(',')
+            .map(|value| SplitPolicy::parse(value.trim()))
+            .collect()
+    }
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            SplitPolicy::RemainderFirst => "remainder_first",
+            SplitPolicy::RemainderLast => "remainder_last",
+            SplitPolicy::Balanced => "balanced",
+            SplitPolicy::LyndonBoundaries => "lyndon_boundaries",
+            SplitPolicy::Proportional => "proportional",
+        }
+    }
+}
+
+pub fn split_factors_in_chunks<T: Ord>(
+    str: &[T],
+    factor_indexes: &[usize],
+    chunking: &Chunking,
+) -> Vec<usize> {
+    check_chunk_size(chunking.chunk_size);
+    let Some(chunk_size) = chunking.chunk_size else {
+        return factor_indexes.to_vec();
+    };
+    let mean_factor_size = str.len() as f64 / factor_indexes.len() as f64;
+    let mut result = Vec::with_capacity(factor_indexes.len());
+    for i in 0..factor_indexes.len() {
+        let factor_index = factor_indexes[i];
+        let factor_end = get_factor_end(factor_indexes, i, str.len());
+        let factor_size = factor_end - factor_index;
+        if !chunking.can_split(factor_size) {
+            result.push(factor_index);
+            continue;
+        }
+        match chunking.split_policy {
+            SplitPolicy::RemainderFirst => {
+                push_chunks_remainder_first(&mut result, factor_index, factor_size, chunk_size);
+            }
+            SplitPolicy::RemainderLast => {
+                let mut chunk_index = factor_index;
+                while chunk_index < factor_end {
+                    result.push(chunk_index);
+                    chunk_index += chunk_size;
                 }
             }
-
-            // Updating "idx_to_is_custom"
-            let mut chars_left_in_icfl_factor = curr_icfl_factor_size;
-            while chars_left_in_icfl_factor > chunk_size {
-                idx_to_is_custom.push(true);
-                chars_left_in_icfl_factor -= 1;
+            SplitPolicy::Balanced => {
+                let num_chunks = factor_size.div_ceil(chunk_size);
+                let num_larger_chunks = factor_size % num_chunks; // One more char, the first ones.
+                let mut chunk_index = factor_index;
+                for i_chunk in 0..num_chunks {
+                    result.push(chunk_index);
+                    chunk_index += factor_size / num_chunks;
+                    if i_chunk < num_larger_chunks {
+                        chunk_index += 1;
+                    }
+                }
             }
-            while chars_left_in_icfl_factor > 0 {
-                idx_to_is_custom.push(false);
-                chars_left_in_icfl_factor -= 1;
+            SplitPolicy::LyndonBoundaries => {
+                let lyndon_indexes = get_cfl_indexes(&str[factor_index..factor_end]);
+                let mut chunk_start = 0; // Relative to the Factor, as Lyndon Indexes.
+                for j in 0..lyndon_indexes.len() {
+                    let lyndon_start = lyndon_indexes[j];
+                    let lyndon_end = get_factor_end(&lyndon_indexes, j, factor_size);
+                    if lyndon_end - lyndon_start > chunk_size {
+                        // Too long, split on its own.
+                        if chunk_start < lyndon_start {
+                            result.push(factor_index + chunk_start);
+                        }
+                        push_chunks_remainder_first(
+                            &mut result,
+                            factor_index + lyndon_start,
+                            lyndon_end - lyndon_start,
+                            chunk_size,
+                        );
+                        chunk_start = lyndon_end;
+                    } else if lyndon_end - chunk_start > chunk_size {
+                        result.push(factor_index + chunk_start);
+                        chunk_start = lyndon_start;
+                    }
+                }
+                if chunk_start < factor_size {
+                    result.push(factor_index + chunk_start);
+                }
             }
-
-            // Updating "idx_to_icfl_factor"
-            for _ in 0..curr_icfl_factor_size {
-                idx_to_icfl_factor.push(i);
+            SplitPolicy::Proportional => {
+                let factor_chunk_size = (chunk_size as f64 * factor_size as f64 / mean_factor_size)
+                    .round()
+                    .clamp(1.0, factor_size as f64)
+                    as usize;
+                push_chunks_remainder_first(
+                    &mut result,
+                    factor_index,
+                    factor_size,
+                    factor_chunk_size,
+                );
             }
         }
+    }
+    result
+}
+
+fn push_chunks_remainder_first(
+    result: &mut Vec<usize>,
+    factor_index: usize,
+    factor_size: usize,
+    chunk_size: usize,
+) {
+    // The smaller chunk is the first one. For example, "DCAABCA" with Chunk Size 3: "D|CAA|BCA".
+    let smaller_chunk_size = factor_size % chunk_size;
+    if smaller_chunk_size > 0 {
+        result.push(factor_index);
+    }
+    let mut chunk_index = factor_index + smaller_chunk_size;
+    while chunk_index < factor_index + factor_size {
+        result.push(chunk_index);
+        chunk_index += chunk_size;
+    }
+}
+
+pub fn get_factor_end(factor_indexes: &[usize], i: usize, str_length: usize) -> usize {
+    if i < factor_indexes.len() - 1 {
+        factor_indexes[i + 1]
     } else {
-        for i in 0..icfl_indexes.len() {
-            let curr_icfl_factor_index = icfl_indexes[i];
+        str_length
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factorization::factorizer::FactorizationStrategy;
+    use crate::factorization::icfl::get_icfl_indexes;
+    use crate::suffix_array::verifier::verify_suffix_array;
+    use crate::test_utils::{for_each_random_string, get_repetitive_strings};
+    use crate::InnovativeSuffixArrayBuilder;
+    use rand::Rng;
+    use SplitPolicy::*;
 
-            // Curr ICFL Factor Size
-            let curr_icfl_factor_size = if i < icfl_indexes.len() - 1 {
-                icfl_indexes[i + 1]
-            } else {
-                str_length
-            } - curr_icfl_factor_index;
+    const SPLIT_POLICIES: [SplitPolicy; 5] = [
+        RemainderFirst,
+        RemainderLast,
+        Balanced,
+        LyndonBoundaries,
+        Proportional,
+    ];
 
-            // Updating "factor_indexes"
-            factor_indexes.push(curr_icfl_factor_index);
+    fn split(
+        split_policy: SplitPolicy,
+        chunk_size: usize,
+        threshold: Option<usize>,
+        factors: &str,
+    ) -> String {
+        // Factors and chunks like "AB|A".
+        let str = factors.replace('|', "");
+        let mut factor_indexes = vec![0];
+        for factor in factors.split('|') {
+            factor_indexes.push(factor_indexes.last().unwrap() + factor.len());
+        }
+        factor_indexes.pop();
+        let chunking = Chunking {
+            chunk_size: Some(chunk_size),
+            threshold,
+            split_policy,
+        };
+        let chunk_indexes = split_factors_in_chunks(str.as_bytes(), &factor_indexes, &chunking);
+        (0..chunk_indexes.len())
+            .map(|i| &str[chunk_indexes[i]..get_factor_end(&chunk_indexes, i, str.len())])
+            .collect::<Vec<_>>()
+            .join("|")
+    }
 
-            // Updating "idx_to_is_custom" and "idx_to_icfl_factor"
-            for _ in 0..curr_icfl_factor_size {
-                idx_to_is_custom.push(false);
-                idx_to_icfl_factor.push(i);
+    #[test]
+    fn split_policies_known_examples() {
+        let paper = "AAA|B|CAABCA|DCAABCA";
+        for (split_policy, expected_chunks) in [
+            (RemainderFirst, "AAA|B|CAA|BCA|D|CAA|BCA"),
+            (RemainderLast, "AAA|B|CAA|BCA|DCA|ABC|A"),
+            (Balanced, "AAA|B|CAA|BCA|DCA|AB|CA"),
+            (LyndonBoundaries, "AAA|B|C|A|ABC|A|DC|A|ABC|A"),
+            // Chunk Sizes 2, 4 and 5 for Factors of 3, 6 and 7 chars (4.25 on average).
+            (Proportional, "A|AA|B|CA|ABCA|DC|AABCA"),
+        ] {
+            assert_eq!(
+                split(split_policy, 3, None, paper),
+                expected_chunks,
+                "{:?}",
+                split_policy
+            );
+        }
+        // Lyndon Factors "C|B|A" and "D|C|B|AZ|AB" are packed up to the Chunk Size.
+        assert_eq!(split(LyndonBoundaries, 2, None, "CBA"), "CB|A");
+        assert_eq!(split(LyndonBoundaries, 5, None, "DCBAZAB"), "DCBAZ|AB");
+    }
+
+    #[test]
+    fn split_policies_respect_threshold() {
+        // With Threshold 6 only the last Factor is split.
+        let paper = "AAA|B|CAABCA|DCAABCA";
+        for (split_policy, expected_chunks) in [
+            (RemainderFirst, "AAA|B|CAABCA|D|CA|AB|CA"),
+            (RemainderLast, "AAA|B|CAABCA|DC|AA|BC|A"),
+            (Balanced, "AAA|B|CAABCA|DC|AA|BC|A"),
+            (LyndonBoundaries, "AAA|B|CAABCA|DC|AA|BC|A"),
+            (Proportional, "AAA|B|CAABCA|D|CAA|BCA"),
+        ] {
+            assert_eq!(
+                split(split_policy, 2, Some(6), paper),
+                expected_chunks,
+                "{:?}",
+                split_policy
+            );
+        }
+    }
+
+    fn assert_chunks_tile_factors(str: &[u8], factor_indexes: &[usize], chunking: &Chunking) {
+        // Each Factor starts a chunk, and only Factors that can be split are: in chunks up to the
+        // Chunk Size (its own one for "Proportional").
+        let chunk_indexes = split_factors_in_chunks(str, factor_indexes, chunking);
+        let message = format!("str={:?}, {:?}", String::from_utf8_lossy(str), chunking);
+        assert!(chunk_indexes.windows(2).all(|w| w[0] < w[1]), "{}", message);
+        for i in 0..factor_indexes.len() {
+            let factor_end = get_factor_end(factor_indexes, i, str.len());
+            let i_from = chunk_indexes.partition_point(|&index| index < factor_indexes[i]);
+            let i_to = chunk_indexes.partition_point(|&index| index < factor_end);
+            assert_eq!(chunk_indexes[i_from], factor_indexes[i], "{}", message);
+            if !chunking.can_split(factor_end - factor_indexes[i]) {
+                assert_eq!(i_to - i_from, 1, "{}", message);
+            } else if chunking.split_policy != Proportional {
+                let max_chunk_size = (i_from..i_to)
+                    .map(|i_chunk| {
+                        get_factor_end(&chunk_indexes, i_chunk, str.len()) - chunk_indexes[i_chunk]
+                    })
+                    .max()
+                    .unwrap();
+                assert!(
+                    max_chunk_size <= chunking.chunk_size.unwrap(),
+                    "{}",
+                    message
+                );
             }
         }
     }
 
-    (
-        //
-        factor_indexes,
-        idx_to_is_custom,
-        idx_to_icfl_factor,
-    )
+    #[test]
+    fn split_policies_tile_factors() {
+        let check = |str: &[u8], chunk_size: usize, threshold: Option<usize>| {
+            for split_policy in SPLIT_POLICIES {
+                let chunking = Chunking {
+                    chunk_size: Some(chunk_size),
+                    threshold,
+                    split_policy,
+                };
+                assert_chunks_tile_factors(str, &get_icfl_indexes(str), &chunking);
+                assert_chunks_tile_factors(str, &get_cfl_indexes(str), &chunking);
+            }
+        };
+        for_each_random_string(23, 500, 1..=120, 1..=4, |str, rng| {
+            let chunk_size = rng.random_range(1..=12);
+            let threshold = rng
+                .random_bool(0.5)
+                .then(|| rng.random_range(0..=2 * chunk_size));
+            check(str, chunk_size, threshold);
+        });
+        for str in get_repetitive_strings(40) {
+            for chunk_size in [1, 2, 3, 5] {
+                check(&str, chunk_size, None);
+            }
+        }
+    }
+
+    #[test]
+    fn split_policies_give_correct_suffix_arrays() {
+        let check = |str: &[u8], chunking: Chunking| {
+            for factorization in [
+                FactorizationStrategy::Icfl,
+                FactorizationStrategy::Cfl,
+                FactorizationStrategy::IcflCfl,
+                FactorizationStrategy::CflIcfl,
+            ] {
+                let suffix_array = InnovativeSuffixArrayBuilder::new(str)
+                    .chunking(chunking)
+                    .factorization(factorization)
+                    .compute()
+                    .suffix_array;
+                let report = verify_suffix_array(str, &suffix_array);
+                assert!(
+                    report.is_ok(),
+                    "str={:?}, {:?}, {}: {}",
+                    String::from_utf8_lossy(str),
+                    chunking,
+                    factorization.get_label(),
+                    report
+                );
+            }
+        };
+        for_each_random_string(23, 100, 1..=80, 1..=4, |str, rng| {
+            for split_policy in SPLIT_POLICIES {
+                let chunk_size = rng.random_range(1..=12);
+                let threshold = rng
+                    .random_bool(0.5)
+                    .then(|| rng.random_range(0..=2 * chunk_size));
+                check(
+                    str,
+                    Chunking {
+                        chunk_size: Some(chunk_size),
+                        threshold,
+                        split_policy,
+                    },
+                );
+            }
+        });
+        for str in get_repetitive_strings(16) {
+            for split_policy in SPLIT_POLICIES {
+                check(
+                    &str,
+                    Chunking {
+                        chunk_size: Some(3),
+                        threshold: None,
+                        split_policy,
+                    },
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Chunk size can't be zero")]
+    fn chunking_rejects_zero_chunk_size() {
+        Chunking::new(Some(0));
+    }
 }
@@ -0,0 +1,325 @@
+use crate::factorization::cfl::get_cfl_indexes;
+use crate::factorization::custom_factorization::{
+    get_factor_end, split_factors_in_chunks, Chunking,
+};
+use crate::factorization::icfl::get_icfl_indexes;
+use crate::prefix_tree::symbol::Symbol;
+use serde::{Deserialize, Serialize};
+
+// FACTORIZATION (PHASE 1 OUTPUT)
+pub struct Factorization {
+    // Canonical Factors: LSs in their last Factor are the only ones not marked as Custom.
+    pub icfl_indexes: Vec<usize>,
+    pub factor_indexes: Vec<usize>,
+    pub idx_to_is_custom: Vec<bool>,
+    pub idx_to_icfl_factor: Vec<usize>,
+}
+
+pub trait Factorizer<T: Symbol> {
+    fn factorize(&self, str: &[T]) -> Factorization;
+}
+
+// FACTORIZATION STRATEGIES
+// The Rules of Phase 3 only hold for LSs of ICFL Factors (see "prefix_tree::rules"): strategies
+// not refining ICFL mark all LSs as Custom, so they are always sorted comparing Global Suffixes.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FactorizationStrategy {
+    Icfl,
+    Cfl,
+    IcflCfl, // ICFL, then CFL of each ICFL Factor.
+    CflIcfl, // CFL, then ICFL of each Lyndon Factor.
+}
+impl FactorizationStrategy {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "icfl" => Ok(FactorizationStrategy::Icfl),
+            "cfl" => Ok(FactorizationStrategy::Cfl),
+            "icfl_cfl" => Ok(FactorizationStrategy::IcflCfl),
+            "cfl_icfl" => Ok(FactorizationStrategy::CflIcfl),
+            _ => Err(format!(
+                "Invalid factorization \"{}\", expected \"icfl\", \"cfl\", \"icfl_cfl\" or \"cfl_icfl\"",
+                value
+            )),
+        }
+    }
+    pub fn parse_list(expression: &str) -> Result<Vec<Self>, String> {
+        // Comma-separated, like "icfl,icfl_cfl".
+        expression
+            .split(',')
+            .map(|value| FactorizationStrategy::parse(value.trim()))
+            .collect()
+    }
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            FactorizationStrategy::Icfl => "icfl",
+            FactorizationStrategy::Cfl => "cfl",
+            FactorizationStrategy::IcflCfl => "icfl_cfl",
+            FactorizationStrategy::CflIcfl => "cfl_icfl",
+        }
+    }
+    pub fn get_factorizer<T: Symbol>(&self, chunking: Chunking) -> Box<dyn Factorizer<T>> {
+        match self {
+            FactorizationStrategy::Icfl => Box::new(IcflFactorizer { chunking }),
+            FactorizationStrategy::Cfl => Box::new(CflFactorizer { chunking }),
+            FactorizationStrategy::IcflCfl => Box::new(IcflCflFactorizer { chunking }),
+            FactorizationStrategy::CflIcfl => Box::new(CflIcflFactorizer { chunking }),
+        }
+    }
+}
+
+// Every strategy has its chunked variant: with a Chunk Size, its Factors are split in Custom
+// Factors of that size (the smaller one first), see "Chunking".
+pub struct IcflFactorizer {
+    pub chunking: Chunking,
+}
+impl<T: Symbol> Factorizer<T> for IcflFactorizer {
+    fn factorize(&self, str: &[T]) -> Factorization {
+        // From "AAA|B|CAABCA|DCAABCA" with Chunk Size 3:
+        //   factor_indexes     = [0, 3, 4, 7, 10, 11, 14] ("AAA|B|CAA|BCA|D|CAA|BCA")
+        //   idx_to_is_custom   = [0,0,0,0,1,1,1,0,0,0,1,1,1,1,0,0,0]
+        //   idx_to_icfl_factor = [0,0,0,1,2,2,2,2,2,2,3,3,3,3,3,3,3]
+        let icfl_indexes = get_icfl_indexes(str);
+        let factor_indexes = split_factors_in_chunks(str, &icfl_indexes, &self.chunking);
+        get_factorization_refining_icfl(icfl_indexes, factor_indexes, str.len())
+    }
+}
+
+pub struct CflFactorizer {
+    pub chunking: Chunking,
+}
+impl<T: Symbol> Factorizer<T> for CflFactorizer {
+    fn factorize(&self, str: &[T]) -> Factorization {
+        let cfl_indexes = get_cfl_indexes(str);
+        let factor_indexes = split_factors_in_chunks(str, &cfl_indexes, &self.chunking);
+        get_factorization_with_only_custom_factors(cfl_indexes, factor_indexes, str.len())
+    }
+}
+
+pub struct IcflCflFactorizer {
+    pub chunking: Chunking,
+}
+impl<T: Symbol> Factorizer<T> for IcflCflFactorizer {
+    fn factorize(&self, str: &[T]) -> Factorization {
+        let icfl_indexes = get_icfl_indexes(str);
+        let sub_factor_indexes = split_factors_using(str, &icfl_indexes, get_cfl_indexes);
+        let factor_indexes = split_factors_in_chunks(str, &sub_factor_indexes, &self.chunking);
+        get_factorization_refining_icfl(icfl_indexes, factor_indexes, str.len())
+    }
+}
+
+pub struct CflIcflFactorizer {
+    pub chunking: Chunking,
+}
+impl<T: Symbol> Factorizer<T> for CflIcflFactorizer {
+    fn factorize(&self, str: &[T]) -> Factorization {
+        let cfl_indexes = get_cfl_indexes(str);
+        let sub_factor_indexes = split_factors_using(str, &cfl_indexes, get_icfl_indexes);
+        let factor_indexes = split_factors_in_chunks(str, &sub_factor_indexes, &self.chunking);
+        get_factorization_with_only_custom_factors(cfl_indexes, factor_indexes, str.len())
+    }
+}
+
+fn split_factors_using<T: Symbol>(
+    str: &[T],
+    factor_indexes: &[usize],
+    get_sub_factor_indexes: fn(&[T]) -> Vec<usize>,
+) -> Vec<usize> {
+    let mut result = Vec::with_capacity(factor_indexes.len());
+    for i in 0..factor_indexes.len() {
+        let factor_index = factor_indexes[i];
+        let factor_end = get_factor_end(factor_indexes, i, str.len());
+        for sub_factor_index in get_sub_factor_indexes(&str[factor_index..factor_end]) {
+            result.push(factor_index + sub_factor_index);
+        }
+    }
+    result
+}
+
+fn get_factorization_refining_icfl(
+    icfl_indexes: Vec<usize>,
+    factor_indexes: Vec<usize>,
+    str_length: usize,
+) -> Factorization {
+    // Each ICFL Factor is split in one or more Factors: the last one is Canonical (as the suffixes
+    // of the ICFL Factor), the others are Custom.
+    let mut idx_to_is_custom = Vec::with_capacity(str_length);
+    let mut idx_to_icfl_factor = Vec::with_capacity(str_length);
+    let mut i_factor = 0;
+    for i in 0..icfl_indexes.len() {
+        let icfl_factor_end = get_factor_end(&icfl_indexes, i, str_length);
+        while i_factor + 1 < factor_indexes.len() && factor_indexes[i_factor + 1] < icfl_factor_end
+        {
+            i_factor += 1;
+        }
+        let last_factor_index = factor_indexes[i_factor];
+        for idx in icfl_indexes[i]..icfl_factor_end {
+            idx_to_is_custom.push(idx < last_factor_index);
+            idx_to_icfl_factor.push(i);
+        }
+    }
+    Factorization {
+        icfl_indexes,
+        factor_indexes,
+        idx_to_is_custom,
+        idx_to_icfl_factor,
+    }
+}
+
+fn get_factorization_with_only_custom_factors(
+    canonical_indexes: Vec<usize>,
+    factor_indexes: Vec<usize>,
+    str_length: usize,
+) -> Factorization {
+    let mut idx_to_icfl_factor = Vec::with_capacity(str_length);
+    for i in 0..canonical_indexes.len() {
+        let factor_end = get_factor_end(&canonical_indexes, i, str_length);
+        for _ in canonical_indexes[i]..factor_end {
+            idx_to_icfl_factor.push(i);
+        }
+    }
+    Factorization {
+        icfl_indexes: canonical_indexes,
+        factor_indexes,
+        idx_to_is_custom: vec![true; str_length],
+        idx_to_icfl_factor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FactorizationStrategy::*;
+
+    fn get_indexes(factors: &str) -> Vec<usize> {
+        // From Factors like "AB|A".
+        let mut result = vec![0];
+        for factor in factors.split('|') {
+            result.push(result.last().unwrap() + factor.len());
+        }
+        result.pop();
+        result
+    }
+    fn check(
+        strategy: FactorizationStrategy,
+        chunk_size: Option<usize>,
+        canonical_factors: &str,
+        factors: &str,
+        is_custom: &str, // "C" for each Custom LS.
+    ) {
+        let str = canonical_factors.replace('|', "");
+        let factorization = strategy
+            .get_factorizer(Chunking::new(chunk_size))
+            .factorize(str.as_bytes());
+        let message = format!(
+            "{} on \"{}\", chunk_size={:?}",
+            strategy.get_label(),
+            str,
+            chunk_size
+        );
+        let icfl_indexes = get_indexes(canonical_factors);
+        assert_eq!(factorization.icfl_indexes, icfl_indexes, "{}", message);
+        assert_eq!(
+            factorization.factor_indexes,
+            get_indexes(factors),
+            "{}",
+            message
+        );
+        let idx_to_is_custom = is_custom.chars().map(|c| c == 'C').collect::<Vec<_>>();
+        assert_eq!(
+            factorization.idx_to_is_custom, idx_to_is_custom,
+            "{}",
+            message
+        );
+        // Each LS in its Canonical Factor.
+        for (idx, &i_icfl_factor) in factorization.idx_to_icfl_factor.iter().enumerate() {
+            let expected = icfl_indexes.partition_point(|&icfl_index| icfl_index <= idx) - 1;
+            assert_eq!(i_icfl_factor, expected, "{}, idx={}", message, idx);
+        }
+    }
+
+    #[test]
+    fn icfl_factorizer() {
+        // The example of the paper, then chunks of 2 with the remainder first.
+        let paper = "AAA|B|CAABCA|DCAABCA";
+        check(Icfl, None, paper, paper, ".................");
+        check(
+            Icfl,
+            Some(2),
+            paper,
+            "A|AA|B|CA|AB|CA|D|CA|AB|CA",
+            "C...CCCC..CCCCC..",
+        );
+        check(Icfl, None, "BA|NANA", "BA|NANA", "......");
+        check(Icfl, Some(2), "BA|NANA", "BA|NA|NA", "..CC..");
+        check(Icfl, Some(2), "A|BABAB", "A|B|AB|AB", ".CCC..");
+        check(Icfl, Some(2), "CBA", "C|BA", "C..");
+    }
+
+    #[test]
+    fn cfl_factorizer() {
+        // Lyndon Factors, all LSs are Custom.
+        check(
+            Cfl,
+            None,
+            "AAABCAABCADCAABC|A",
+            "AAABCAABCADCAABC|A",
+            "CCCCCCCCCCCCCCCCC",
+        );
+        check(
+            Cfl,
+            Some(2),
+            "AAABCAABCADCAABC|A",
+            "AA|AB|CA|AB|CA|DC|AA|BC|A",
+            "CCCCCCCCCCCCCCCCC",
+        );
+        check(Cfl, None, "B|AN|AN|A", "B|AN|AN|A", "CCCCCC");
+        check(Cfl, Some(2), "AB|AB|AB", "AB|AB|AB", "CCCCCC");
+        check(Cfl, None, "C|B|A", "C|B|A", "CCC");
+    }
+
+    #[test]
+    fn icfl_cfl_factorizer() {
+        // Only the last Lyndon Factor of each ICFL Factor is Canonical.
+        let paper = "AAA|B|CAABCA|DCAABCA";
+        check(
+            IcflCfl,
+            None,
+            paper,
+            "A|A|A|B|C|AABC|A|D|C|AABC|A",
+            "CC..CCCCC.CCCCCC.",
+        );
+        check(
+            IcflCfl,
+            Some(2),
+            paper,
+            "A|A|A|B|C|AA|BC|A|D|C|AA|BC|A",
+            "CC..CCCCC.CCCCCC.",
+        );
+        check(IcflCfl, None, "BA|NANA", "B|A|N|AN|A", "C.CCC.");
+        check(IcflCfl, None, "A|BABAB", "A|B|AB|AB", ".CCC..");
+        check(IcflCfl, None, "CBA", "C|B|A", "CC.");
+    }
+
+    #[test]
+    fn cfl_icfl_factorizer() {
+        // ICFL of each Lyndon Factor, all LSs are Custom.
+        let lyndon = "AAABCAABCADCAABC|A";
+        check(
+            CflIcfl,
+            None,
+            lyndon,
+            "AAA|B|CAABCA|DCAABC|A",
+            "CCCCCCCCCCCCCCCCC",
+        );
+        check(
+            CflIcfl,
+            Some(2),
+            lyndon,
+            "A|AA|B|CA|AB|CA|DC|AA|BC|A",
+            "CCCCCCCCCCCCCCCCC",
+        );
+        check(CflIcfl, None, "B|AN|AN|A", "B|A|N|A|N|A", "CCCCCC");
+        check(CflIcfl, None, "AB|AB|AB", "A|B|A|B|A|B", "CCCCCC");
+    }
+}
@@ -1,91 +1,73 @@
-use std::i32;
+pub fn get_icfl_indexes<T: Ord + Copy>(str: &[T]) -> Vec<usize> {
+    // ICFL(w) = w if w is an Inverse Lyndon Word, otherwise w = pv, with ICFL(v) = (m1', ..., mk')
+    // and p' = rb the Bounded Right Extension of p in w:
+    //   ICFL(w) = (p) + ICFL(v)          if |m1'| > |r|
+    //             (pm1', m2', ..., mk')  otherwise
+    // Iterative version working on indexes only. The recursion is always on a suffix of the
+    // string ("bre + y" starts where "p" ends), so it is unrolled in:
+    // * a forward pass, collecting the start of every "p" with "|r|";
+    // * a backward pass, where each "p" becomes a Factor or is prepended to the first Factor of
+    //   the rest (as the recursive version does returning from the recursion, see the tests).
+    let mut p_starts_and_r_sizes = Vec::new();
+    let mut failure_function = Vec::new(); // Reused by every step.
+    let mut start = 0;
+    while let Some(x_end) = icfl_find_prefix_end(&str[start..]) {
+        let (p_size, r_size) = icfl_find_bre_sizes(&str[start..], x_end, &mut failure_function);
+        p_starts_and_r_sizes.push((start, r_size));
+        start += p_size;
+    }
 
-pub fn get_icfl_indexes(str: &[char]) -> Vec<usize> {
-    let icfl_factors_in_bytes = icfl_bytes(str);
-    let mut result = Vec::with_capacity(icfl_factors_in_bytes.len());
-    let mut i = 0;
-    // Reference so that it will be freed after.
-    for factor_bytes in &icfl_factors_in_bytes {
-        result.push(i);
-        i += factor_bytes.len();
+    // From the last Factor to the first one: the rest is an Inverse Lyndon Word.
+    let mut reversed_result = vec![start];
+    for &(p_start, r_size) in p_starts_and_r_sizes.iter().rev() {
+        let first_factor_start = reversed_result[reversed_result.len() - 1];
+        let first_factor_end = if reversed_result.len() > 1 {
+            reversed_result[reversed_result.len() - 2]
+        } else {
+            str.len()
+        };
+        if first_factor_end - first_factor_start > r_size {
+            // |m1'| > |r|
+            reversed_result.push(p_start);
+        } else {
+            // l[0] = p + l[0]
+            let last = reversed_result.len() - 1;
+            reversed_result[last] = p_start;
+        }
     }
-    result
+    reversed_result.reverse();
+    reversed_result
 }
 
 pub fn icfl(s: &str) -> Vec<String> {
     let mut result = Vec::new();
 
-    let str = s.chars().collect::<Vec<_>>();
-    let factors_in_bytes = icfl_bytes(&str);
-    for factor_bytes in factors_in_bytes {
-        let factor = String::from_iter(factor_bytes);
+    // NOTE: Works using chars as bytes.
+    let str = s.as_bytes();
+    let icfl_indexes = get_icfl_indexes(str);
+    for i in 0..icfl_indexes.len() {
+        let factor_end = if i < icfl_indexes.len() - 1 {
+            icfl_indexes[i + 1]
+        } else {
+            str.len()
+        };
+        let factor = String::from_utf8(str[icfl_indexes[i]..factor_end].to_vec()).unwrap();
         result.push(factor);
     }
 
     result
 }
 
-const ZERO_CHAR: char = '0';
-fn icfl_bytes(w: &[char]) -> Vec<Vec<char>> {
-    /**
-    input: a string w
-    output: the inverse factorization of w obtained with the algorithm ICFL
-        If w is an inverse lyndon word, ICFL(w) = w otherwise we have w=pv
-        and ICFL(v) = (m1', ..., mk') and p' bounded right extension of p in w.
-        ICFL(w) = (p) + ICFL(v)         if p' = rb <= m1'
-                  (pm1', m2', ..., mk') if m1' <= r
-    */
-    let (x, y) = icfl_find_prefix(w);
-
-    // if x == w + '0' // Should be.
-    if x.len() == w.len() + 1 && x[w.len()] == ZERO_CHAR {
-        let mut i = 0;
-        while i < w.len() && x[i] == w[i] {
-            i += 1;
-        }
-        if i == w.len() {
-            return vec![w.to_vec()];
-        }
-    }
-    let (p, bre, last) = icfl_find_bre(&x, &y);
-
-    // l = icfl(bre + y); // Should be.
-    let mut bre_plus_y = bre;
-    bre_plus_y.extend(y);
-    let mut l = icfl_bytes(&bre_plus_y);
-    if l[0].len() as i32 > last {
-        // |m1'| > |r|
-        l.insert(0, p);
-    } else {
-        // l[0] = p + l[0]; // Should be.
-        for i in 0..p.len() {
-            l[0].insert(0, p[p.len() - 1 - i]);
-        }
-    }
-    l
-}
-
-fn icfl_find_prefix(w: &[char]) -> (Vec<char>, Vec<char>) {
-    /**
-    input: a string w
-    output: (x, y) where x = w0, y = '' if w in an inverse Lyndon word
-        w = xy, x = pp' where (p, p') ∈ Pref_bre(w), otherwise.
-        p is an inverse Lyndon word which is a proper prefix of w = pv;
-        p' is the bounded right extension of p in w.
-        A bounder right extension is a proper prefix of v such that:
-            - p' is an inverse Lyndon word
-            - pz' is an inverse Lyndon word for each proper prefix z' of p'
-            - pp' is not an inverse Lyndon word
-            - p << p' (p < p' and p is not a proper prefix of p')
-        Pref_bre(w) = {(p, p') | p is an inverse Lyndon word which is a non
-            empty proper prefix of w }
-    */
+fn icfl_find_prefix_end<T: Ord + Copy>(w: &[T]) -> Option<usize> {
+    // None if "w" is an Inverse Lyndon Word, otherwise the last index of "x", where "w = xy" and
+    // "x = pp'" with "(p, p') ∈ Pref_bre(w)":
+    // * "p" is an Inverse Lyndon Word which is a non empty proper prefix of "w = pv";
+    // * "p'" is the Bounded Right Extension of "p" in "w", a proper prefix of "v" such that
+    //   "p'" and "pz'" (for each proper prefix "z'" of "p'") are Inverse Lyndon Words, "pp'" is
+    //   not one, and "p << p'" ("p < p'" and "p" is not a proper prefix of "p'").
     let n = w.len();
-    if n == 1 {
-        // return (w + '0', '');
-        let mut new_w = w.to_vec();
-        new_w.push(ZERO_CHAR);
-        return (new_w, Vec::new());
+    if n <= 1 {
+        return None;
     }
 
     let mut i = 0;
@@ -99,73 +81,41 @@ fn icfl_find_prefix(w: &[char]) -> (Vec<char>, Vec<char>) {
         j += 1;
     }
 
-    if j == n - 1 {
-        if w[j] <= w[i] {
-            // return (w + '0', '');
-            let mut new_w = w.to_vec();
-            new_w.push(ZERO_CHAR);
-            return (new_w, Vec::new());
-        }
+    if j == n - 1 && w[j] <= w[i] {
+        return None;
     }
-
-    (
-        //
-        (&w[0..j + 1]).to_vec(),
-        (&w[j + 1..]).to_vec(),
-    )
+    Some(j)
 }
 
-fn icfl_find_bre(x: &[char], y: &[char]) -> (Vec<char>, Vec<char>, i32) {
-    /**
-    input: (x, y) where w = xy is not an inverse Lyndon word;
-        x = pp' = raurb, (p, p') ∈ Pref_bre(w)
-    output: (p, p', y, last) = (rau, rb, y, |r|)
-    */
-    let mut w = Vec::with_capacity(x.len() + y.len());
-    w.extend_from_slice(x);
-    w.extend_from_slice(y);
-
-    let n = x.len() as i32 - 1;
-    let f = icfl_get_failure_function(x, x.len() - 1); // Border(raur)
-
-    let mut i = n - 1;
-    let mut last = n;
-
-    while i >= 0 {
-        let i_usize = i as usize;
-        if w[f[i_usize]] < x[x.len() - 1] {
-            last = f[i_usize] as i32 - 1;
+fn icfl_find_bre_sizes<T: Ord + Copy>(
+    w: &[T],
+    x_end: usize,
+    failure_function: &mut Vec<usize>,
+) -> (usize, usize) {
+    // With "x = w[..=x_end] = pp' = raurb", returns "(|p|, |r|)" where "p = rau" and "p' = rb":
+    // "bre + y" is the suffix of "w" after "p".
+    let n = x_end;
+    icfl_fill_failure_function(&w[..n], failure_function); // Border(raur)
+
+    let mut last = n; // "|r|" once the loop is over.
+    let mut i = n;
+    while i > 0 {
+        let border = failure_function[i - 1];
+        if w[border] < w[n] {
+            last = border;
         }
-        i = f[i_usize] as i32 - 1;
+        i = border;
     }
 
-    let mut sep1_i32 = n - last - 1;
-    if sep1_i32 < 0 {
-        sep1_i32 += w.len() as i32;
-    }
-    let sep1_usize = sep1_i32 as usize;
-    let sep2_usize = (n + 1) as usize;
-
-    let res1 = (&w[0..sep1_usize]).to_vec();
-    let res2 = if sep2_usize > sep1_usize {
-        (&w[sep1_usize..sep2_usize]).to_vec()
-    } else {
-        Vec::new()
-    };
-
-    (res1, res2, last + 1)
+    (n - last, last)
 }
 
-fn icfl_get_failure_function(s: &[char], s_inner_size: usize) -> Vec<usize> {
-    // Here we fake that "m" is the size of "s", since the caller is most likely to exclude the last
-    // item of "s".
-    // let m = s.len();
-    let m = s_inner_size;
-
-    let mut f = Vec::with_capacity(m);
-    for _ in 0..m {
-        f.push(0);
-    }
+fn icfl_fill_failure_function<T: Ord + Copy>(s: &[T], f: &mut Vec<usize>) {
+    // Failure Function of KMP ("f[i]" is the longest proper Border of "s[..=i]"), in a buffer
+    // reused by every step.
+    let m = s.len();
+    f.clear();
+    f.resize(m, 0);
 
     let mut i = 1;
     let mut j = 0;
@@ -181,6 +131,122 @@ fn icfl_get_failure_function(s: &[char], s_inner_size: usize) -> Vec<usize> {
             i += 1;
         }
     }
+}
 
-    f
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::for_each_random_string;
+
+    fn get_icfl_indexes_recursive<T: Ord + Copy>(str: &[T]) -> Vec<usize> {
+        // Reference implementation, used to validate "get_icfl_indexes".
+        let mut result = Vec::new();
+        let mut i = 0;
+        for factor in icfl_recursive(str) {
+            result.push(i);
+            i += factor.len();
+        }
+        result
+    }
+
+    fn icfl_recursive<T: Ord + Copy>(w: &[T]) -> Vec<Vec<T>> {
+        // The recursive definition of ICFL, see "get_icfl_indexes".
+        let Some(x_end) = icfl_find_prefix_end(w) else {
+            return vec![w.to_vec()];
+        };
+        let (p_size, r_size) = icfl_find_bre_sizes(w, x_end, &mut Vec::new());
+        let (p, bre_plus_y) = w.split_at(p_size);
+        let mut l = icfl_recursive(bre_plus_y);
+        if l[0].len() > r_size {
+            // |m1'| > |r|
+            l.insert(0, p.to_vec());
+        } else {
+            // l[0] = p + l[0]
+            l[0].splice(0..0, p.iter().copied());
+        }
+        l
+    }
+
+    fn assert_same_as_recursive(str: &[u8]) {
+        let icfl_indexes = get_icfl_indexes(str);
+        assert_eq!(
+            icfl_indexes,
+            get_icfl_indexes_recursive(str),
+            "str={:?}",
+            String::from_utf8_lossy(str)
+        );
+    }
+
+    fn get_all_strings(alphabet_size: u8, length: u32) -> Vec<Vec<u8>> {
+        (0..(alphabet_size as usize).pow(length))
+            .map(|mut code| {
+                (0..length)
+                    .map(|_| {
+                        let symbol = b'A' + (code % alphabet_size as usize) as u8;
+                        code /= alphabet_size as usize;
+                        symbol
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn icfl_known_examples() {
+        assert_eq!(
+            icfl("AAABCAABCADCAABCA"),
+            vec!["AAA", "B", "CAABCA", "DCAABCA"]
+        );
+        assert_eq!(get_icfl_indexes(b"AAABCAABCADCAABCA"), vec![0, 3, 4, 10]);
+        // Inverse Lyndon Words are a single Factor.
+        assert_eq!(get_icfl_indexes(b"A"), vec![0]);
+        assert_eq!(get_icfl_indexes(b"AAAA"), vec![0]);
+        assert_eq!(get_icfl_indexes(b"DCBA"), vec![0]);
+        assert_eq!(get_icfl_indexes(b"BABA"), vec![0]);
+    }
+
+    #[test]
+    fn icfl_factors_are_inverse_lyndon_words() {
+        // Every proper suffix of a Factor is smaller than the Factor.
+        for length in 1..=7 {
+            for str in get_all_strings(3, length) {
+                let icfl_indexes = get_icfl_indexes(&str);
+                for i in 0..icfl_indexes.len() {
+                    let factor_end = if i < icfl_indexes.len() - 1 {
+                        icfl_indexes[i + 1]
+                    } else {
+                        str.len()
+                    };
+                    let factor = &str[icfl_indexes[i]..factor_end];
+                    assert!(
+                        (1..factor.len()).all(|j| factor[j..] < *factor),
+                        "str={:?}, factor={:?}",
+                        String::from_utf8_lossy(&str),
+                        String::from_utf8_lossy(factor),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn icfl_same_as_recursive_on_all_small_strings() {
+        for length in 1..=12 {
+            for str in get_all_strings(2, length) {
+                assert_same_as_recursive(&str);
+            }
+        }
+        for length in 1..=8 {
+            for str in get_all_strings(3, length) {
+                assert_same_as_recursive(&str);
+            }
+        }
+    }
+
+    #[test]
+    fn icfl_same_as_recursive_on_random_strings() {
+        for_each_random_string(21, 2000, 1..=120, 1..=4, |str, _| {
+            assert_same_as_recursive(str);
+        });
+    }
 }
@@ -1,11 +1,14 @@
+pub mod auto_chunk_size;
 pub mod cfl;
 pub mod custom_factorization;
+pub mod factorizer;
 pub mod icfl;
 pub mod logging;
+pub mod stats;
 
-pub fn get_max_factor_size(factor_indexes: &Vec<usize>, str_length: usize) -> usize {
-    let mut result = factor_indexes[0];
-    for i in 1..factor_indexes.len() - 1 {
+pub fn get_max_factor_size(factor_indexes: &[usize], str_length: usize) -> usize {
+    let mut result = 0;
+    for i in 0..factor_indexes.len() - 1 {
         let curr_factor_size = factor_indexes[i + 1] - factor_indexes[i];
         if result < curr_factor_size {
             result = curr_factor_size;
@@ -17,3 +20,21 @@ pub fn get_max_factor_size(factor_indexes: &Vec<usize>, str_length: usize) -> us
     }
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_factor_size_includes_first_factor() {
+        // Factors "AAAAA|B|CC": the first one is the longest, and it used to be skipped.
+        assert_eq!(get_max_factor_size(&[0, 5, 6], 8), 5);
+    }
+
+    #[test]
+    fn max_factor_size_includes_middle_and_last_factors() {
+        assert_eq!(get_max_factor_size(&[0, 1, 5, 6], 8), 4);
+        assert_eq!(get_max_factor_size(&[0, 1, 2], 9), 7);
+        assert_eq!(get_max_factor_size(&[0], 3), 3);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::factorization::custom_factorization::get_factor_end;
+use crate::factorization::factorizer::Factorization;
+use crate::factorization::get_max_factor_size;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// FACTORIZATION STATISTICS
+// Shape of the Phase 1 output, to be compared with the timings of Phases 2 and 3.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FactorizationStats {
+    pub num_icfl_factors: usize, // Canonical Factors, as "icfl_indexes".
+    pub num_factors: usize,
+    pub num_custom_factors: usize,
+    pub max_factor_size: usize,
+    pub mean_factor_size: f64,
+    pub num_custom_positions: usize,
+    pub custom_positions_fraction: f64, // Of positions with "idx_to_is_custom" set.
+    pub factor_size_histogram: BTreeMap<usize, usize>, // Factor size to number of Factors.
+}
+
+pub fn compute_factorization_stats(
+    factorization: &Factorization,
+    str_length: usize,
+) -> FactorizationStats {
+    let factor_indexes = &factorization.factor_indexes;
+    if str_length == 0 {
+        return FactorizationStats {
+            num_icfl_factors: 0,
+            num_factors: 0,
+            num_custom_factors: 0,
+            max_factor_size: 0,
+            mean_factor_size: 0.0,
+            num_custom_positions: 0,
+            custom_positions_fraction: 0.0,
+            factor_size_histogram: BTreeMap::new(),
+        };
+    }
+    let mut num_custom_factors = 0;
+    let mut factor_size_histogram = BTreeMap::new();
+    for i in 0..factor_indexes.len() {
+        let factor_index = factor_indexes[i];
+        let factor_size = get_factor_end(factor_indexes, i, str_length) - factor_index;
+        *factor_size_histogram.entry(factor_size).or_insert(0) += 1;
+        // All LSs of a Factor are Custom or none is.
+        if factorization.idx_to_is_custom[factor_index] {
+            num_custom_factors += 1;
+        }
+    }
+    let num_custom_positions = factorization
+        .idx_to_is_custom
+        .iter()
+        .filter(|&&is_custom| is_custom)
+        .count();
+    FactorizationStats {
+        num_icfl_factors: factorization.icfl_indexes.len(),
+        num_factors: factor_indexes.len(),
+        num_custom_factors,
+        max_factor_size: get_max_factor_size(factor_indexes, str_length),
+        mean_factor_size: str_length as f64 / factor_indexes.len() as f64,
+        num_custom_positions,
+        custom_positions_fraction: num_custom_positions as f64 / str_length as f64,
+        factor_size_histogram,
+    }
+}
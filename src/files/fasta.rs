@@ -1,21 +1,176 @@
 use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 
-pub fn get_fasta_content(filepath: String) -> String {
-    let file = File::open(filepath.as_str())
-        .expect(format!("Unable to read {} FASTA file", filepath).as_str());
-    let reader = BufReader::new(file);
+pub struct FastaRecord {
+    pub id: String,          // Header up to the first whitespace (without ">").
+    pub description: String, // Rest of the header.
+    pub sequence: String,    // As in the file, without line endings.
+}
+
+// Which records make the content, and whether it is turned to upper case.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FastaSelection {
+    pub records: FastaRecordSelection,
+    pub uppercase: bool,
+}
+impl FastaSelection {
+    pub fn new(records: FastaRecordSelection) -> Self {
+        Self {
+            records,
+            uppercase: false,
+        }
+    }
+    pub fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+}
+impl Default for FastaSelection {
+    fn default() -> Self {
+        // Only the first record, as it is in the file.
+        Self::new(FastaRecordSelection::RecordAt(0))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FastaRecordSelection {
+    // Only the record at the given position (starting from 0).
+    RecordAt(usize),
+    // Only the record with the given id.
+    RecordWithId(String),
+    // All records one after another, optionally with a separator between them.
+    Concatenate(Option<char>),
+}
+impl FastaRecordSelection {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        // Like "0", "id:chr1", "all" or "all:$".
+        if let Some(id) = value.strip_prefix("id:") {
+            if id.is_empty() {
+                return Err("Missing record id after \"id:\"".to_string());
+            }
+            return Ok(FastaRecordSelection::RecordWithId(id.to_string()));
+        }
+        if value == "all" {
+            return Ok(FastaRecordSelection::Concatenate(None));
+        }
+        if let Some(separator) = value.strip_prefix("all:") {
+            let mut chars = separator.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(separator), None) => Ok(FastaRecordSelection::Concatenate(Some(separator))),
+                _ => Err(format!(
+                    "Invalid separator \"{}\", expected a single char after \"all:\"",
+                    separator
+                )),
+            };
+        }
+        value
+            .parse()
+            .map(FastaRecordSelection::RecordAt)
+            .map_err(|_| {
+                format!(
+                    "Invalid record selection \"{}\", expected a position, \"id:ID\", \"all\" or \"all:SEPARATOR\"",
+                    value
+                )
+            })
+    }
+}
+
+pub fn get_fasta_content(filepath: String, selection: &FastaSelection) -> String {
+    // Header lines never end up in the content, whatever the selection.
+    let records = read_fasta_records(filepath.clone());
+    select_fasta_content(records, selection).unwrap_or_else(|| match &selection.records {
+        FastaRecordSelection::RecordWithId(id) => {
+            panic!(
+                "Unable to find record \"{}\" in {} FASTA file",
+                id, filepath
+            )
+        }
+        FastaRecordSelection::RecordAt(i_record) => {
+            panic!(
+                "Unable to find record {} in {} FASTA file",
+                i_record, filepath
+            )
+        }
+        FastaRecordSelection::Concatenate(_) => unreachable!(),
+    })
+}
 
-    let mut lines = reader.lines();
-    lines.next(); // Skip first line (because it's the "header" of the FASTA format file)
+pub fn select_fasta_content(
+    records: Vec<FastaRecord>,
+    selection: &FastaSelection,
+) -> Option<String> {
+    // None if the selected record is not there.
+    let mut result = match &selection.records {
+        FastaRecordSelection::RecordAt(i_record) => records.into_iter().nth(*i_record)?.sequence,
+        FastaRecordSelection::RecordWithId(id) => {
+            records
+                .into_iter()
+                .find(|record| &record.id == id)?
+                .sequence
+        }
+        FastaRecordSelection::Concatenate(separator) => {
+            let mut result = String::new();
+            for (i_record, record) in records.iter().enumerate() {
+                if i_record > 0 {
+                    if let Some(separator) = separator {
+                        result.push(*separator);
+                    }
+                }
+                result.push_str(&record.sequence);
+            }
+            result
+        }
+    };
+    if selection.uppercase {
+        result.make_ascii_uppercase();
+    }
+    Some(result)
+}
 
-    let mut result = String::new();
-    while let Some(line_result) = lines.next() {
+pub fn read_fasta_records(filepath: String) -> Vec<FastaRecord> {
+    let file = File::open(filepath.as_str())
+        .unwrap_or_else(|_| panic!("Unable to read {} FASTA file", filepath));
+    parse_fasta_records(BufReader::new(file))
+}
+
+pub fn parse_fasta_records<R: BufRead>(reader: R) -> Vec<FastaRecord> {
+    let mut records = Vec::new();
+    for line_result in reader.lines() {
         let line_string = line_result.unwrap();
-        let line_str = line_string.as_str();
-        result.push_str(line_str);
+        // Also removes "\r" from Windows line endings.
+        let line_str = line_string.trim();
+        if line_str.is_empty() || line_str.starts_with(';') {
+            // Blank line or comment.
+            continue;
+        }
+        if let Some(header) = line_str.strip_prefix('>') {
+            let (id, description) = match header.split_once(char::is_whitespace) {
+                Some((id, description)) => (id, description.trim()),
+                None => (header, ""),
+            };
+            records.push(FastaRecord {
+                id: id.to_string(),
+                description: description.to_string(),
+                sequence: String::new(),
+            });
+        } else {
+            if records.is_empty() {
+                // Sequence without any header before it.
+                records.push(FastaRecord {
+                    id: String::new(),
+                    description: String::new(),
+                    sequence: String::new(),
+                });
+            }
+            let record = records.last_mut().unwrap();
+            for curr_char in line_str.chars() {
+                if !curr_char.is_whitespace() {
+                    record.sequence.push(curr_char);
+                }
+            }
+        }
     }
-    result
+    records
 }
 
 pub fn save_fasta_with_content(filepath: String, whole_line: String) {
@@ -24,7 +179,7 @@ pub fn save_fasta_with_content(filepath: String, whole_line: String) {
     let string_length = whole_line.len();
 
     let mut f = File::create(filepath).expect("Unable to create file");
-    f.write(format!(">GENERATED, with {} chars\n", string_length).as_bytes())
+    f.write_all(format!(">GENERATED, with {} chars\n", string_length).as_bytes())
         .expect("Unable to write first line");
 
     let mut chars = whole_line.chars();
@@ -33,7 +188,7 @@ pub fn save_fasta_with_content(filepath: String, whole_line: String) {
     while i < string_length {
         // Write one line at a time
         let mut curr_line = String::new();
-        while let Some(curr_char) = chars.next() {
+        for curr_char in chars.by_ref() {
             curr_line.push(curr_char);
             if curr_line.len() < max_chars_in_line {
                 // Ok.
@@ -43,7 +198,8 @@ pub fn save_fasta_with_content(filepath: String, whole_line: String) {
         }
         i += curr_line.len();
         curr_line.push('\n');
-        f.write(curr_line.as_bytes()).expect("Unable to write line");
+        f.write_all(curr_line.as_bytes())
+            .expect("Unable to write line");
         if curr_line.len() < max_chars_in_line {
             // No more chars.
             break;
@@ -53,3 +209,142 @@ pub fn save_fasta_with_content(filepath: String, whole_line: String) {
         println!(" > Written chars {}/{}", i, string_length);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Vec<FastaRecord> {
+        parse_fasta_records(content.as_bytes())
+    }
+
+    #[test]
+    fn parse_multiple_records() {
+        let records = parse(">seq1 first one\nACGT\nAC\n>seq2\nGGG\n>seq3  third\tone \nT\n");
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].id, "seq1");
+        assert_eq!(records[0].description, "first one");
+        assert_eq!(records[0].sequence, "ACGTAC");
+        assert_eq!(records[1].id, "seq2");
+        assert_eq!(records[1].description, "");
+        assert_eq!(records[1].sequence, "GGG");
+        assert_eq!(records[2].id, "seq3");
+        assert_eq!(records[2].description, "third\tone");
+        assert_eq!(records[2].sequence, "T");
+    }
+
+    #[test]
+    fn parse_skips_blank_lines_and_comments() {
+        let records = parse(";comment\n\n>seq1\nAC\n\n  \n;another comment\nGT\n\n>seq2\n\nA\n");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].sequence, "ACGT");
+        assert_eq!(records[1].sequence, "A");
+    }
+
+    #[test]
+    fn parse_keeps_case_and_drops_line_endings() {
+        let records = parse(">seq1\r\nacGT\r\nNn\r\n");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id, "seq1");
+        assert_eq!(records[0].sequence, "acGTNn");
+    }
+
+    #[test]
+    fn parse_sequence_without_header() {
+        let records = parse("ACGT\n>seq2\nT\n");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "");
+        assert_eq!(records[0].sequence, "ACGT");
+    }
+
+    #[test]
+    fn select_records() {
+        let content = ">seq1\nacgt\n>seq2\nGG\n";
+        let select = |selection: FastaSelection| select_fasta_content(parse(content), &selection);
+        assert_eq!(
+            select(FastaSelection::new(FastaRecordSelection::RecordAt(1))),
+            Some("GG".to_string())
+        );
+        assert_eq!(
+            select(FastaSelection::new(FastaRecordSelection::RecordAt(2))),
+            None
+        );
+        assert_eq!(
+            select(FastaSelection::new(FastaRecordSelection::RecordWithId(
+                "seq1".to_string()
+            ))),
+            Some("acgt".to_string())
+        );
+        assert_eq!(
+            select(FastaSelection::new(FastaRecordSelection::Concatenate(
+                Some('$')
+            ))),
+            Some("acgt$GG".to_string())
+        );
+        assert_eq!(
+            select(FastaSelection::new(FastaRecordSelection::Concatenate(None)).uppercase(true)),
+            Some("ACGTGG".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_record_selection() {
+        assert_eq!(
+            FastaRecordSelection::parse("2"),
+            Ok(FastaRecordSelection::RecordAt(2))
+        );
+        assert_eq!(
+            FastaRecordSelection::parse("id:chr1"),
+            Ok(FastaRecordSelection::RecordWithId("chr1".to_string()))
+        );
+        assert_eq!(
+            FastaRecordSelection::parse("all"),
+            Ok(FastaRecordSelection::Concatenate(None))
+        );
+        assert_eq!(
+            FastaRecordSelection::parse("all:$"),
+            Ok(FastaRecordSelection::Concatenate(Some('$')))
+        );
+        for value in ["", "-1", "first", "id:", "all:", "all:$$"] {
+            assert!(FastaRecordSelection::parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn multiple_records_file_has_no_headers_in_content() {
+        let filepath = std::env::temp_dir()
+            .join(format!(
+                "ptsaca-multiple-records-{}.fasta",
+                std::process::id()
+            ))
+            .to_str()
+            .unwrap()
+            .to_string();
+        std::fs::write(
+            &filepath,
+            ">seq1 first one\nACGT\nAC\n>seq2 second\nGGG\n>seq3\nTT\n",
+        )
+        .unwrap();
+        let selections = [
+            FastaSelection::default(),
+            FastaSelection::new(FastaRecordSelection::RecordAt(2)),
+            FastaSelection::new(FastaRecordSelection::RecordWithId("seq2".to_string())),
+            FastaSelection::new(FastaRecordSelection::Concatenate(None)),
+            FastaSelection::new(FastaRecordSelection::Concatenate(Some('$'))),
+        ];
+        let contents: Vec<String> = selections
+            .iter()
+            .map(|selection| get_fasta_content(filepath.clone(), selection))
+            .collect();
+        std::fs::remove_file(&filepath).unwrap();
+        assert_eq!(
+            contents,
+            vec!["ACGTAC", "TT", "GGG", "ACGTACGGGTT", "ACGTAC$GGG$TT"]
+        );
+        for content in contents {
+            assert!(!content.contains('>'));
+            assert!(!content.contains("seq"));
+            assert!(!content.contains("one"));
+        }
+    }
+}
@@ -1,11 +1,18 @@
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::fs::File;
-use std::io::Write;
+use std::io::{BufReader, Write};
 
 pub fn dump_json_in_file<T: Serialize>(file_format: &T, filepath: String) {
     let json = serde_json::to_string_pretty(file_format).unwrap();
     let mut file = File::create(filepath).expect("Unable to create file");
-    file.write(json.as_bytes())
+    file.write_all(json.as_bytes())
         .expect("Unable to write JSON string");
     file.flush().expect("Unable to flush file");
 }
+
+pub fn read_json_from_file<T: DeserializeOwned>(filepath: String) -> T {
+    let file = File::open(filepath.as_str())
+        .unwrap_or_else(|_| panic!("Unable to read {} JSON file", filepath));
+    serde_json::from_reader(BufReader::new(file)).expect("Unable to parse JSON file")
+}
@@ -1,3 +1,4 @@
 pub mod fasta;
 pub mod json;
 pub mod paths;
+pub mod results;
@@ -1,101 +1,210 @@
+pub const DEFAULT_RESULTS_FOLDER: &str = "results";
+pub const DEFAULT_PLOTS_FOLDER: &str = "plots";
+
 pub fn get_path_in_generated_folder(filename: &str) -> String {
     format!("generated/{}.fasta", filename)
 }
 
-pub fn get_path_for_project_folder(filename: &str) -> String {
-    format!("results/{}", filename)
+pub fn get_path_for_project_folder(results_folder: &str, filename: &str) -> String {
+    format!("{}/{}", results_folder, filename)
 }
-pub fn get_path_for_plots_folder(filename: &str) -> String {
-    format!("plots/{}", filename)
+pub fn get_path_for_plots_folder(plots_folder: &str, filename: &str) -> String {
+    format!("{}/{}", plots_folder, filename)
 }
+pub fn get_path_for_memory_plot_file(
+    plots_folder: &str,
+    fasta_file_name: &str,
+    min_chunk_size: usize,
+    max_chunk_size: usize,
+) -> String {
+    get_path_for_plots_folder(
+        plots_folder,
+        &format!(
+            "plot-{}-memory-chunks-{}-{}.png",
+            fasta_file_name, min_chunk_size, max_chunk_size
+        ),
+    )
+}
+
 pub fn get_path_for_plot_file(
+    plots_folder: &str,
     fasta_file_name: &str,
     min_chunk_size: usize,
     max_chunk_size: usize,
 ) -> String {
-    get_path_for_plots_folder(&format!(
-        "plot-{}-chunks-{}-{}.png",
-        fasta_file_name, min_chunk_size, max_chunk_size
-    ))
+    get_path_for_plots_folder(
+        plots_folder,
+        &format!(
+            "plot-{}-chunks-{}-{}.png",
+            fasta_file_name, min_chunk_size, max_chunk_size
+        ),
+    )
 }
 
-pub fn get_path_for_project_factorization_file(filename: &str, chunk_size: usize) -> String {
+pub fn get_path_for_project_factorization_file(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
     format!(
         "{}/{}-{}-a-fact.txt",
-        get_path_for_project_folder(filename),
+        get_path_for_project_folder(results_folder, filename),
+        filename,
+        chunk_size
+    )
+}
+
+pub fn get_path_for_project_factorization_stats_file_json(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
+    format!(
+        "{}/{}-{}-a-fact-stats.json",
+        get_path_for_project_folder(results_folder, filename),
         filename,
         chunk_size
     )
 }
 
-pub fn get_path_for_project_tree_file(filename: &str, chunk_size: usize) -> String {
+pub fn get_path_for_project_tree_file(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
     format!(
         "{}/{}-{}-a-tree.txt",
-        get_path_for_project_folder(filename),
+        get_path_for_project_folder(results_folder, filename),
         filename,
         chunk_size
     )
 }
 
-pub fn get_path_for_project_full_tree_file(filename: &str, chunk_size: usize) -> String {
+pub fn get_path_for_project_full_tree_file(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
     format!(
         "{}/{}-{}-aa-full_tree.txt",
-        get_path_for_project_folder(filename),
+        get_path_for_project_folder(results_folder, filename),
         filename,
         chunk_size
     )
 }
 
-pub fn get_path_for_project_mini_tree_file(filename: &str, chunk_size: usize) -> String {
+pub fn get_path_for_project_mini_tree_file(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
     format!(
         "{}/{}-{}-aa-mini-tree.txt",
-        get_path_for_project_folder(filename),
+        get_path_for_project_folder(results_folder, filename),
         filename,
         chunk_size
     )
 }
 
-pub fn get_path_for_project_prefix_tree_file(filename: &str, chunk_size: usize) -> String {
+pub fn get_path_for_project_prefix_tree_file(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
     format!(
         "{}/{}-{}-b-prefix-tree.txt",
-        get_path_for_project_folder(filename),
+        get_path_for_project_folder(results_folder, filename),
         filename,
         chunk_size
     )
 }
 
-pub fn get_path_for_project_suffix_array_file(filename: &str, chunk_size: usize) -> String {
+pub fn get_path_for_project_suffix_array_file(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
     format!(
         "{}/{}-{}-z-suffix-array.txt",
-        get_path_for_project_folder(filename),
+        get_path_for_project_folder(results_folder, filename),
         filename,
         chunk_size
     )
 }
 
-pub fn get_path_for_project_outcome_file_json(filename: &str, chunk_size: usize) -> String {
+pub fn get_path_for_project_outcome_file_json(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
     format!(
         "{}/{}-{}-za-execution.json",
-        get_path_for_project_folder(filename),
+        get_path_for_project_folder(results_folder, filename),
         filename,
         chunk_size
     )
 }
 
-pub fn get_path_for_project_timing_file_json(filename: &str, chunk_size: usize) -> String {
+pub fn get_path_for_project_timing_file_json(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
     format!(
         "{}/{}-{}-zb-timing.json",
-        get_path_for_project_folder(filename),
+        get_path_for_project_folder(results_folder, filename),
+        filename,
+        chunk_size
+    )
+}
+
+pub fn get_path_for_project_rules_oracle_file_json(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
+    format!(
+        "{}/{}-{}-zd-rules-oracle.json",
+        get_path_for_project_folder(results_folder, filename),
+        filename,
+        chunk_size
+    )
+}
+
+pub fn get_path_for_project_memory_file_json(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
+    format!(
+        "{}/{}-{}-zc-memory.json",
+        get_path_for_project_folder(results_folder, filename),
         filename,
         chunk_size
     )
 }
 
-pub fn get_path_for_project_monitor_file(filename: &str, chunk_size: usize) -> String {
+pub fn get_path_for_project_monitor_file(
+    results_folder: &str,
+    filename: &str,
+    chunk_size: usize,
+) -> String {
     format!(
         "{}/{}-{}-zz-monitor.txt",
-        get_path_for_project_folder(filename),
+        get_path_for_project_folder(results_folder, filename),
         filename,
         chunk_size
     )
 }
+
+pub fn get_path_for_project_suite_stats_file_json(
+    results_folder: &str,
+    filename: &str,
+    run_name: &str, // See "SuiteConfig::get_run_name".
+) -> String {
+    format!(
+        "{}/{}-suite-stats.json",
+        get_path_for_project_folder(results_folder, filename),
+        run_name,
+    )
+}
@@ -0,0 +1,196 @@
+use crate::factorization::logging::log_factorization;
+use crate::factorization::stats::FactorizationStats;
+use crate::files::json::dump_json_in_file;
+use crate::files::paths::{
+    get_path_for_project_factorization_file, get_path_for_project_factorization_stats_file_json,
+    get_path_for_project_folder, get_path_for_project_memory_file_json,
+    get_path_for_project_mini_tree_file, get_path_for_project_outcome_file_json,
+    get_path_for_project_rules_oracle_file_json, get_path_for_project_suffix_array_file,
+    get_path_for_project_timing_file_json,
+};
+use crate::new_suffix_array::InnovativeSuffixArrayLogger;
+use crate::prefix_tree::layout::PrefixTree;
+use crate::prefix_tree::log_execution_info::ExecutionInfoFileFormat;
+use crate::prefix_tree::log_execution_outcome::ExecutionOutcomeFileFormat;
+use crate::prefix_tree::log_memory_usage::MemoryUsageFileFormat;
+use crate::prefix_tree::log_rules_oracle::RulesOracleReportFileFormat;
+use crate::prefix_tree::logging::{log_tree, TreeLogMode};
+use crate::prefix_tree::monitor::ExecutionInfo;
+use crate::prefix_tree::symbol::Symbol;
+use crate::suffix_array::logger::{log_suffix_array, make_sure_directory_exist};
+
+// Logger writing into "{results_folder}/{fasta_file_name}/", as the Suite always did.
+pub struct ResultsFolderLogger<'a> {
+    results_folder: &'a str,
+    fasta_file_name: &'a str,
+    log_execution: bool,
+    log_fact: bool,
+    log_trees_and_suffix_array: bool,
+}
+impl<'a> ResultsFolderLogger<'a> {
+    pub fn new(
+        results_folder: &'a str,
+        fasta_file_name: &'a str,
+        log_execution: bool,
+        log_fact: bool,
+        log_trees_and_suffix_array: bool,
+    ) -> Self {
+        Self {
+            results_folder,
+            fasta_file_name,
+            log_execution,
+            log_fact,
+            log_trees_and_suffix_array,
+        }
+    }
+}
+impl<'a, T: Symbol> InnovativeSuffixArrayLogger<T> for ResultsFolderLogger<'a> {
+    fn log_factorization(
+        &mut self,
+        chunk_size: Option<usize>,
+        str: &[T],
+        icfl_indexes: &[usize],
+        factor_indexes: &[usize],
+    ) {
+        if self.log_fact {
+            make_sure_directory_exist(get_path_for_project_folder(
+                self.results_folder,
+                self.fasta_file_name,
+            ));
+            log_factorization(
+                factor_indexes,
+                icfl_indexes,
+                str,
+                get_path_for_project_factorization_file(
+                    self.results_folder,
+                    self.fasta_file_name,
+                    chunk_size.unwrap_or(0),
+                ),
+            );
+        }
+    }
+    fn log_factorization_stats(
+        &mut self,
+        chunk_size: Option<usize>,
+        factorization_stats: &FactorizationStats,
+    ) {
+        if self.log_fact {
+            make_sure_directory_exist(get_path_for_project_folder(
+                self.results_folder,
+                self.fasta_file_name,
+            ));
+            dump_json_in_file(
+                factorization_stats,
+                get_path_for_project_factorization_stats_file_json(
+                    self.results_folder,
+                    self.fasta_file_name,
+                    chunk_size.unwrap_or(0),
+                ),
+            );
+        }
+    }
+    fn log_tree(&mut self, chunk_size: Option<usize>, tree: &dyn PrefixTree<T>) {
+        if self.log_trees_and_suffix_array {
+            let chunk_size_or_zero = chunk_size.unwrap_or(0);
+            make_sure_directory_exist(get_path_for_project_folder(
+                self.results_folder,
+                self.fasta_file_name,
+            ));
+            /*
+            log_tree(
+                tree,
+                TreeLogMode::Tree,
+                get_path_for_project_tree_file(self.results_folder, self.fasta_file_name, chunk_size_or_zero),
+            );
+            log_tree(
+                tree,
+                TreeLogMode::FullTree,
+                get_path_for_project_full_tree_file(self.results_folder, self.fasta_file_name, chunk_size_or_zero),
+            );
+            */
+            log_tree(
+                tree,
+                TreeLogMode::MiniTree,
+                get_path_for_project_mini_tree_file(
+                    self.results_folder,
+                    self.fasta_file_name,
+                    chunk_size_or_zero,
+                ),
+            );
+        }
+    }
+    fn log_suffix_array(&mut self, chunk_size: Option<usize>, suffix_array: &[usize]) {
+        if self.log_trees_and_suffix_array {
+            make_sure_directory_exist(get_path_for_project_folder(
+                self.results_folder,
+                self.fasta_file_name,
+            ));
+            log_suffix_array(
+                suffix_array,
+                get_path_for_project_suffix_array_file(
+                    self.results_folder,
+                    self.fasta_file_name,
+                    chunk_size.unwrap_or(0),
+                ),
+            );
+        }
+    }
+    fn log_execution_info(&mut self, chunk_size: Option<usize>, execution_info: &ExecutionInfo) {
+        // Rules Oracle JSON file: only there if requested, so always written.
+        if let Some(rules_oracle) = &execution_info.rules_oracle {
+            make_sure_directory_exist(get_path_for_project_folder(
+                self.results_folder,
+                self.fasta_file_name,
+            ));
+            dump_json_in_file(
+                &RulesOracleReportFileFormat::new(rules_oracle),
+                get_path_for_project_rules_oracle_file_json(
+                    self.results_folder,
+                    self.fasta_file_name,
+                    chunk_size.unwrap_or(0),
+                ),
+            );
+        }
+        if self.log_execution {
+            let chunk_size_or_zero = chunk_size.unwrap_or(0);
+            make_sure_directory_exist(get_path_for_project_folder(
+                self.results_folder,
+                self.fasta_file_name,
+            ));
+            // Execution Outcome JSON file
+            let execution_outcome_file_format =
+                ExecutionOutcomeFileFormat::new(&execution_info.execution_outcome);
+            dump_json_in_file(
+                &execution_outcome_file_format,
+                get_path_for_project_outcome_file_json(
+                    self.results_folder,
+                    self.fasta_file_name,
+                    chunk_size_or_zero,
+                ),
+            );
+
+            // Execution Timing JSON file
+            let execution_timing_file_format =
+                ExecutionInfoFileFormat::new(&execution_info.execution_timing);
+            dump_json_in_file(
+                &execution_timing_file_format,
+                get_path_for_project_timing_file_json(
+                    self.results_folder,
+                    self.fasta_file_name,
+                    chunk_size_or_zero,
+                ),
+            );
+
+            // Memory Usage JSON file
+            let memory_usage_file_format = MemoryUsageFileFormat::new(&execution_info.memory_usage);
+            dump_json_in_file(
+                &memory_usage_file_format,
+                get_path_for_project_memory_file_json(
+                    self.results_folder,
+                    self.fasta_file_name,
+                    chunk_size_or_zero,
+                ),
+            );
+        }
+    }
+}
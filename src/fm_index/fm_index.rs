@@ -0,0 +1,221 @@
+use crate::bwt::bwt::bwt_from_suffix_array;
+use crate::fm_index::occurrences::OccurrencesTable;
+use crate::fm_index::sampled_suffix_array::SampledSuffixArray;
+use crate::new_suffix_array::InnovativeSuffixArrayBuilder;
+use crate::prefix_tree::symbol::Symbol;
+
+pub const DEFAULT_OCC_SAMPLE_RATE: usize = 64;
+pub const DEFAULT_SA_SAMPLE_RATE: usize = 32;
+
+// FM-INDEX
+// Built on the BWT of "str + $" (see "bwt::bwt"), so rows go from 0 to "str.len()" included,
+// and row 0 is always the Suffix "$".
+pub struct FmIndex<T> {
+    str_length: usize,
+    alphabet: Vec<T>, // Sorted, without duplicates.
+    c: Vec<usize>,    // Rows starting with a Symbol smaller than the given Symbol id.
+    occurrences: OccurrencesTable,
+    sampled_suffix_array: SampledSuffixArray,
+}
+impl<T: Symbol> FmIndex<T> {
+    pub fn new(str: &[T], chunk_size: Option<usize>) -> Self {
+        let suffix_array = if str.is_empty() {
+            Vec::new()
+        } else {
+            InnovativeSuffixArrayBuilder::new(str)
+                .chunk_size(chunk_size)
+                .compute()
+                .suffix_array
+        };
+        Self::from_suffix_array(
+            str,
+            &suffix_array,
+            DEFAULT_OCC_SAMPLE_RATE,
+            DEFAULT_SA_SAMPLE_RATE,
+        )
+        .unwrap()
+    }
+    pub fn from_suffix_array(
+        str: &[T],
+        suffix_array: &[usize],
+        occ_sample_rate: usize,
+        sa_sample_rate: usize,
+    ) -> Result<Self, String> {
+        // Rows and Global Suffixes are sampled every "sample_rate", so it can't be zero.
+        if occ_sample_rate == 0 || sa_sample_rate == 0 {
+            return Err(format!(
+                "Sample rates can't be zero (occurrences: {}, suffix array: {})",
+                occ_sample_rate, sa_sample_rate
+            ));
+        }
+        let str_length = str.len();
+        let bwt = bwt_from_suffix_array(str, suffix_array);
+
+        // Alphabet and C Array
+        let mut alphabet = str.to_vec();
+        alphabet.sort();
+        alphabet.dedup();
+        let mut symbol_counts = vec![0; alphabet.len()];
+        for symbol in str {
+            symbol_counts[alphabet.binary_search(symbol).unwrap()] += 1;
+        }
+        let mut c = Vec::with_capacity(alphabet.len());
+        let mut rows_before = 1; // The Sentinel row.
+        for symbol_count in symbol_counts {
+            c.push(rows_before);
+            rows_before += symbol_count;
+        }
+
+        // Occurrences
+        let occurrences = OccurrencesTable::new(&bwt, &alphabet, occ_sample_rate);
+
+        // Sampled Suffix Array (of "str + $", where the Suffix "$" comes first)
+        let mut suffix_array_with_sentinel = Vec::with_capacity(str_length + 1);
+        suffix_array_with_sentinel.push(str_length);
+        suffix_array_with_sentinel.extend(suffix_array);
+        let sampled_suffix_array =
+            SampledSuffixArray::new(&suffix_array_with_sentinel, sa_sample_rate);
+
+        Ok(Self {
+            str_length,
+            alphabet,
+            c,
+            occurrences,
+            sampled_suffix_array,
+        })
+    }
+
+    pub fn count(&self, pattern: &[T]) -> usize {
+        let (row_from, row_to) = self.backward_search(pattern);
+        row_to - row_from
+    }
+    pub fn locate(&self, pattern: &[T]) -> Vec<usize> {
+        let (row_from, row_to) = self.backward_search(pattern);
+        let mut result = Vec::with_capacity(row_to - row_from);
+        for row in row_from..row_to {
+            // Going backwards with LF-Mapping until a sampled row: every step is one Symbol.
+            let mut curr_row = row;
+            let mut steps = 0;
+            loop {
+                if let Some(gs_index) = self.sampled_suffix_array.get(curr_row) {
+                    result.push(gs_index + steps);
+                    break;
+                }
+                curr_row = self.lf(curr_row);
+                steps += 1;
+            }
+        }
+        result.sort();
+        result
+    }
+    fn backward_search(&self, pattern: &[T]) -> (usize, usize) {
+        // Returns the window of rows (from incl., to excl.) starting with the pattern.
+        if pattern.is_empty() {
+            // Every Global Suffix, not the Suffix "$".
+            return (1, self.str_length + 1);
+        }
+        let mut row_from = 0;
+        let mut row_to = self.str_length + 1;
+        for symbol in pattern.iter().rev() {
+            let symbol_id = match self.alphabet.binary_search(symbol) {
+                Ok(symbol_id) => symbol_id,
+                Err(_) => return (0, 0),
+            };
+            row_from = self.c[symbol_id] + self.occurrences.occ(symbol_id, row_from);
+            row_to = self.c[symbol_id] + self.occurrences.occ(symbol_id, row_to);
+            if row_from >= row_to {
+                return (0, 0);
+            }
+        }
+        (row_from, row_to)
+    }
+    fn lf(&self, row: usize) -> usize {
+        match self.occurrences.get_symbol_id(row) {
+            Some(symbol_id) => self.c[symbol_id] + self.occurrences.occ(symbol_id, row),
+            None => 0, // The Sentinel is the first Symbol.
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{for_each_random_string, get_repetitive_strings};
+    use rand::Rng;
+
+    fn get_occurrences_via_naive_scan(str: &[u8], pattern: &[u8]) -> Vec<usize> {
+        (0..str.len())
+            .filter(|&i| str[i..].starts_with(pattern))
+            .collect()
+    }
+
+    fn assert_same_as_naive_scan(fm_index: &FmIndex<u8>, str: &[u8], pattern: &[u8]) {
+        let expected = get_occurrences_via_naive_scan(str, pattern);
+        assert_eq!(
+            fm_index.locate(pattern),
+            expected,
+            "str={:?}, pattern={:?}",
+            String::from_utf8_lossy(str),
+            String::from_utf8_lossy(pattern)
+        );
+        assert_eq!(fm_index.count(pattern), expected.len());
+    }
+
+    #[test]
+    fn fm_index_on_random_strings() {
+        for_each_random_string(6, 100, 1..=120, 1..=4, |str, rng| {
+            // Small sample rates, so that "locate" walks with LF-Mapping.
+            let mut suffix_array = (0..str.len()).collect::<Vec<_>>();
+            suffix_array.sort_by_key(|&gs_index| &str[gs_index..]);
+            let fm_index = FmIndex::from_suffix_array(
+                str,
+                &suffix_array,
+                rng.random_range(1..=8),
+                rng.random_range(1..=8),
+            )
+            .unwrap();
+            for _ in 0..20 {
+                let pattern_length = rng.random_range(1..=6);
+                let pattern = if str.len() > pattern_length && rng.random_bool(0.5) {
+                    let from = rng.random_range(0..str.len() - pattern_length);
+                    str[from..from + pattern_length].to_vec()
+                } else {
+                    (0..pattern_length)
+                        .map(|_| b'A' + rng.random_range(0..5))
+                        .collect()
+                };
+                assert_same_as_naive_scan(&fm_index, str, &pattern);
+            }
+        });
+    }
+
+    #[test]
+    fn fm_index_on_repetitive_strings() {
+        for str in get_repetitive_strings(30) {
+            let fm_index = FmIndex::new(&str[..], Some(3));
+            for pattern in [&b"A"[..], b"AB", b"ABA", b"BAA", b"AAAA", b"Z"] {
+                assert_same_as_naive_scan(&fm_index, &str, pattern);
+            }
+        }
+    }
+
+    #[test]
+    fn fm_index_edge_cases() {
+        let str = b"ABRACADABRA";
+        let fm_index = FmIndex::new(&str[..], None);
+        assert_same_as_naive_scan(&fm_index, str, b"");
+        assert_same_as_naive_scan(&fm_index, str, b"ABRACADABRAA");
+        assert_same_as_naive_scan(&fm_index, str, b"0");
+        let fm_index = FmIndex::new(&b""[..], None);
+        assert_eq!(fm_index.count(b"A"), 0);
+        assert!(fm_index.locate(b"").is_empty());
+    }
+
+    #[test]
+    fn fm_index_rejects_zero_sample_rates() {
+        let str = b"ACGT";
+        let suffix_array = [0, 1, 2, 3];
+        assert!(FmIndex::from_suffix_array(&str[..], &suffix_array, 0, 4).is_err());
+        assert!(FmIndex::from_suffix_array(&str[..], &suffix_array, 4, 0).is_err());
+    }
+}
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod fm_index;
+pub mod occurrences;
+pub mod sampled_suffix_array;
@@ -0,0 +1,62 @@
+use crate::bwt::bwt::Bwt;
+
+// OCCURRENCES (RANK) TABLE
+// "occ(symbol_id, row)" = how many times the Symbol appears in the BWT last column before "row".
+// Counts are stored every "sample_rate" rows for each Symbol of the alphabet, the rest is counted
+// scanning the last column from the closest checkpoint.
+pub struct OccurrencesTable {
+    // Last column as Symbol ids (index in the sorted alphabet), None for the Sentinel.
+    symbol_ids: Vec<Option<usize>>,
+    alphabet_size: usize,
+    sample_rate: usize,
+    // Checkpoint "k" holds the counts before row "k * sample_rate", one per Symbol id.
+    checkpoints: Vec<usize>,
+}
+impl OccurrencesTable {
+    pub fn new<T: Ord + Copy>(bwt: &Bwt<T>, alphabet: &[T], sample_rate: usize) -> Self {
+        let num_rows = bwt.len_with_sentinel();
+        let alphabet_size = alphabet.len();
+        let mut symbol_ids = Vec::with_capacity(num_rows);
+        for row in 0..num_rows {
+            symbol_ids.push(
+                bwt.get(row)
+                    .map(|symbol| alphabet.binary_search(&symbol).unwrap()),
+            );
+        }
+
+        let mut checkpoints = Vec::with_capacity((num_rows / sample_rate + 1) * alphabet_size);
+        let mut counts = vec![0; alphabet_size];
+        for (row, &symbol_id) in symbol_ids.iter().enumerate() {
+            if row % sample_rate == 0 {
+                checkpoints.extend_from_slice(&counts);
+            }
+            if let Some(symbol_id) = symbol_id {
+                counts[symbol_id] += 1;
+            }
+        }
+        if num_rows.is_multiple_of(sample_rate) {
+            // Checkpoint for "row = num_rows", the end of the last column.
+            checkpoints.extend_from_slice(&counts);
+        }
+
+        Self {
+            symbol_ids,
+            alphabet_size,
+            sample_rate,
+            checkpoints,
+        }
+    }
+    pub fn get_symbol_id(&self, row: usize) -> Option<usize> {
+        self.symbol_ids[row]
+    }
+    pub fn occ(&self, symbol_id: usize, row: usize) -> usize {
+        let checkpoint = row / self.sample_rate;
+        let mut result = self.checkpoints[checkpoint * self.alphabet_size + symbol_id];
+        for i_row in checkpoint * self.sample_rate..row {
+            if self.symbol_ids[i_row] == Some(symbol_id) {
+                result += 1;
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,47 @@
+// SAMPLED SUFFIX ARRAY
+// Keeps the Suffix Array item only for rows whose Global Suffix index is a multiple of
+// "sample_rate". Sampled rows are marked in a bit vector with cumulative ranks per word, so the
+// position of a row among the samples is found in constant time.
+pub struct SampledSuffixArray {
+    sample_rate: usize,
+    samples: Vec<usize>,
+    marks: Vec<u64>,
+    marks_ranks: Vec<usize>, // Number of marked rows before each word.
+}
+impl SampledSuffixArray {
+    pub fn new(suffix_array_with_sentinel: &[usize], sample_rate: usize) -> Self {
+        let num_rows = suffix_array_with_sentinel.len();
+        let mut samples = Vec::with_capacity(num_rows / sample_rate + 1);
+        let mut marks = vec![0u64; num_rows / 64 + 1];
+        for (row, &gs_index) in suffix_array_with_sentinel.iter().enumerate() {
+            if gs_index % sample_rate == 0 {
+                samples.push(gs_index);
+                marks[row / 64] |= 1 << (row % 64);
+            }
+        }
+        let mut marks_ranks = Vec::with_capacity(marks.len());
+        let mut rank = 0;
+        for &word in &marks {
+            marks_ranks.push(rank);
+            rank += word.count_ones() as usize;
+        }
+        Self {
+            sample_rate,
+            samples,
+            marks,
+            marks_ranks,
+        }
+    }
+    pub fn get_sample_rate(&self) -> usize {
+        self.sample_rate
+    }
+    pub fn get(&self, row: usize) -> Option<usize> {
+        let word = self.marks[row / 64];
+        let bit = 1 << (row % 64);
+        if word & bit == 0 {
+            return None;
+        }
+        let rank_in_word = (word & (bit - 1)).count_ones() as usize;
+        Some(self.samples[self.marks_ranks[row / 64] + rank_in_word])
+    }
+}
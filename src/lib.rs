@@ -0,0 +1,22 @@
+pub mod bwt;
+pub mod cli;
+pub mod extra;
+pub mod factorization;
+pub mod files;
+pub mod fm_index;
+pub mod new_suffix_array;
+pub mod plot;
+pub mod prefix_tree;
+pub mod suffix_array;
+pub mod suite;
+pub mod suite_config;
+pub mod suite_stats;
+#[cfg(test)]
+mod test_utils;
+
+pub use new_suffix_array::{
+    InnovativeSuffixArrayBuilder, InnovativeSuffixArrayComputationResults,
+    InnovativeSuffixArrayLogger,
+};
+pub use prefix_tree::monitor::{ExecutionInfo, Monitor};
+pub use suffix_array::lcp::LcpStrategy;
@@ -1,165 +1,403 @@
-use crate::factorization::custom_factorization::get_custom_factors_and_more_using_chunk_size;
-use crate::factorization::icfl::get_icfl_indexes;
-use crate::factorization::logging::log_factorization;
-use crate::files::json::dump_json_in_file;
-use crate::files::paths::{
-    get_path_for_project_factorization_file, get_path_for_project_folder,
-    get_path_for_project_full_tree_file, get_path_for_project_mini_tree_file,
-    get_path_for_project_outcome_file_json, get_path_for_project_suffix_array_file,
-    get_path_for_project_timing_file_json, get_path_for_project_tree_file,
+use crate::factorization::auto_chunk_size::{select_chunk_size, AutoChunkSize};
+use crate::factorization::custom_factorization::{check_chunk_size, Chunking, SplitPolicy};
+use crate::factorization::factorizer::{Factorization, FactorizationStrategy};
+use crate::factorization::stats::{compute_factorization_stats, FactorizationStats};
+use crate::files::results::ResultsFolderLogger;
+use crate::prefix_tree::arena_tree::{create_arena_tree, create_arena_tree_parallel};
+use crate::prefix_tree::layout::{PrefixTree, TreeLayout};
+use crate::prefix_tree::monitor::{read_peak_rss_kb, reset_peak_rss, ExecutionInfo, Monitor};
+use crate::prefix_tree::print::get_string_clone;
+use crate::prefix_tree::symbol::Symbol;
+use crate::prefix_tree::tree::{create_tree, create_tree_parallel};
+use crate::suffix_array::lcp::{
+    compute_lcp_array_from_bounds, compute_lcp_array_kasai, LcpStrategy,
 };
-use crate::prefix_tree::log_execution_info::ExecutionInfoFileFormat;
-use crate::prefix_tree::log_execution_outcome::ExecutionOutcomeFileFormat;
-use crate::prefix_tree::logging::{log_tree, TreeLogMode};
-use crate::prefix_tree::monitor::{ExecutionInfo, Monitor};
-use crate::prefix_tree::tree::create_tree;
-use crate::suffix_array::logger::{log_suffix_array, make_sure_directory_exist};
 
 // INNOVATIVE SUFFIX ARRAY
 pub struct InnovativeSuffixArrayComputationResults {
+    pub chunk_size: Option<usize>, // The one used, selected in Phase 1 with an Auto Chunk Size.
     pub suffix_array: Vec<usize>,
+    pub lcp_array: Option<Vec<usize>>,
     pub execution_info: ExecutionInfo,
+    pub factorization_stats: FactorizationStats,
 }
+
+// LOGGING SINK
+// Every method is optional: the computation never touches the file system by itself, it only
+// hands its intermediate products to the sink (if any was given to the builder).
+pub trait InnovativeSuffixArrayLogger<T: Symbol> {
+    fn log_factorization(
+        &mut self,
+        _chunk_size: Option<usize>,
+        _str: &[T],
+        _icfl_indexes: &[usize],
+        _factor_indexes: &[usize],
+    ) {
+    }
+    fn log_factorization_stats(
+        &mut self,
+        _chunk_size: Option<usize>,
+        _factorization_stats: &FactorizationStats,
+    ) {
+    }
+    fn log_tree(&mut self, _chunk_size: Option<usize>, _tree: &dyn PrefixTree<T>) {}
+    fn log_suffix_array(&mut self, _chunk_size: Option<usize>, _suffix_array: &[usize]) {}
+    fn log_execution_info(&mut self, _chunk_size: Option<usize>, _execution_info: &ExecutionInfo) {}
+}
+
+// BUILDER
+// Works on any alphabet: "&[u8]" for bytes (as DNA), "&[char]" for Unicode text, "&[u16]" or
+// "&[u32]" for token ids...
+pub struct InnovativeSuffixArrayBuilder<'a, T: Symbol> {
+    str: &'a [T],
+    chunk_size: Option<usize>,
+    chunk_threshold: Option<usize>,
+    split_policy: SplitPolicy,
+    auto_chunk_size: Option<AutoChunkSize>,
+    factorization: FactorizationStrategy,
+    lcp_strategy: Option<LcpStrategy>,
+    num_threads: usize,
+    tree_num_threads: usize,
+    tree_layout: TreeLayout,
+    rules_oracle: bool,
+    reset_peak_rss: bool,
+    monitor: Option<&'a mut Monitor>,
+    logger: Option<&'a mut dyn InnovativeSuffixArrayLogger<T>>,
+}
+impl<'a, T: Symbol> InnovativeSuffixArrayBuilder<'a, T> {
+    pub fn new(str: &'a [T]) -> Self {
+        Self {
+            str,
+            chunk_size: None,
+            chunk_threshold: None,
+            split_policy: SplitPolicy::RemainderFirst,
+            auto_chunk_size: None,
+            factorization: FactorizationStrategy::Icfl,
+            lcp_strategy: None,
+            num_threads: 1,
+            tree_num_threads: 1,
+            tree_layout: TreeLayout::Nested,
+            rules_oracle: false,
+            reset_peak_rss: false,
+            monitor: None,
+            logger: None,
+        }
+    }
+    pub fn chunk_size(mut self, chunk_size: Option<usize>) -> Self {
+        check_chunk_size(chunk_size);
+        self.chunk_size = chunk_size;
+        self
+    }
+    pub fn chunk_threshold(mut self, chunk_threshold: Option<usize>) -> Self {
+        // Only Factors longer than the Threshold are split in Custom Factors.
+        self.chunk_threshold = chunk_threshold;
+        self
+    }
+    pub fn split_policy(mut self, split_policy: SplitPolicy) -> Self {
+        // Where Factors are split in Custom Factors, "RemainderFirst" by default.
+        self.split_policy = split_policy;
+        self
+    }
+    pub fn chunking(self, chunking: Chunking) -> Self {
+        self.chunk_size(chunking.chunk_size)
+            .chunk_threshold(chunking.threshold)
+            .split_policy(chunking.split_policy)
+    }
+    pub fn auto_chunk_size(mut self, auto_chunk_size: Option<AutoChunkSize>) -> Self {
+        // The Chunk Size is selected in Phase 1, ignoring the given one.
+        self.auto_chunk_size = auto_chunk_size;
+        self
+    }
+    pub fn factorization(mut self, factorization: FactorizationStrategy) -> Self {
+        // Factorization of Phase 1, "ICFL" by default. The Chunk Size applies to any of them.
+        self.factorization = factorization;
+        self
+    }
+    pub fn lcp(mut self, lcp_strategy: Option<LcpStrategy>) -> Self {
+        self.lcp_strategy = lcp_strategy;
+        self
+    }
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        // Threads for Phase 3, with "1" (default) it runs on the current thread only.
+        self.num_threads = num_threads.max(1);
+        self
+    }
+    pub fn tree_num_threads(mut self, tree_num_threads: usize) -> Self {
+        // Threads for Phase 2, with "1" (default) the Prefix Tree is built serially.
+        self.tree_num_threads = tree_num_threads.max(1);
+        self
+    }
+    pub fn tree_layout(mut self, tree_layout: TreeLayout) -> Self {
+        // Memory layout of the Prefix Tree, "Nested" by default.
+        self.tree_layout = tree_layout;
+        self
+    }
+    pub fn rules_oracle(mut self, rules_oracle: bool) -> Self {
+        // Every decision of the Rules in Phase 3 is checked against the actual string comparison,
+        // mismatches are collected in the Execution Info (slow, for validation only).
+        self.rules_oracle = rules_oracle;
+        self
+    }
+    pub fn reset_peak_rss(mut self, reset_peak_rss: bool) -> Self {
+        // The peak Resident Set Size of the whole process is reset before the computation and
+        // read after it, in the Memory Usage. Off by default: a library call should not touch
+        // process-wide state, it is meant for benchmarks that own the process (Suite, CLI).
+        self.reset_peak_rss = reset_peak_rss;
+        self
+    }
+    pub fn monitor(mut self, monitor: &'a mut Monitor) -> Self {
+        // The given Monitor is left populated after the computation, otherwise a private one is
+        // used just to produce the Execution Info.
+        self.monitor = Some(monitor);
+        self
+    }
+    pub fn logger(mut self, logger: &'a mut dyn InnovativeSuffixArrayLogger<T>) -> Self {
+        self.logger = Some(logger);
+        self
+    }
+    pub fn compute(self) -> InnovativeSuffixArrayComputationResults {
+        let str = self.str;
+        let lcp_strategy = self.lcp_strategy;
+        let mut logger = self.logger;
+        let mut own_monitor = Monitor::new();
+        let monitor = match self.monitor {
+            Some(monitor) => monitor,
+            None => &mut own_monitor,
+        };
+        if self.rules_oracle {
+            monitor.enable_rules_oracle();
+        }
+        // Without the reset the peak could be the one of a previous computation.
+        let is_peak_rss_reset = self.reset_peak_rss && reset_peak_rss();
+        monitor.whole_duration.start();
+
+        // FACTORIZATION
+        monitor.p1_fact.start();
+        let mut chunking = Chunking {
+            chunk_size: self.chunk_size,
+            threshold: self.chunk_threshold,
+            split_policy: self.split_policy,
+        };
+        if let Some(auto_chunk_size) = self.auto_chunk_size {
+            let factor_indexes = self
+                .factorization
+                .get_factorizer(Chunking::new(None))
+                .factorize(str)
+                .factor_indexes;
+            let given_chunking = chunking;
+            chunking.chunk_size = select_chunk_size(
+                auto_chunk_size,
+                str,
+                &factor_indexes,
+                |sample, chunk_size| {
+                    let et = InnovativeSuffixArrayBuilder::new(sample)
+                        .chunking(Chunking {
+                            chunk_size,
+                            ..given_chunking
+                        })
+                        .factorization(self.factorization)
+                        .tree_layout(self.tree_layout)
+                        .compute()
+                        .execution_info
+                        .execution_timing;
+                    et.p2_tree.dur + et.p3_sa.dur
+                },
+            );
+        }
+        let chunk_size = chunking.chunk_size;
+        let factorization = self.factorization.get_factorizer(chunking).factorize(str);
+        monitor.p1_fact.stop();
+
+        // + Extra
+        let factorization_stats = compute_factorization_stats(&factorization, str.len());
+        // - Extra
+
+        let Factorization {
+            icfl_indexes,
+            factor_indexes,
+            idx_to_is_custom,
+            idx_to_icfl_factor,
+        } = factorization;
+
+        // + Extra
+        if let Some(logger) = &mut logger {
+            logger.log_factorization(chunk_size, str, &icfl_indexes, &factor_indexes);
+            logger.log_factorization_stats(chunk_size, &factorization_stats);
+        }
+        // - Extra
+
+        // TREE
+        monitor.p2_tree.start();
+        let tree: Box<dyn PrefixTree<T> + 'a> = match self.tree_layout {
+            TreeLayout::Nested if self.tree_num_threads > 1 => Box::new(create_tree_parallel(
+                str,
+                &factor_indexes,
+                &icfl_indexes,
+                &idx_to_is_custom,
+                monitor,
+                self.tree_num_threads,
+            )),
+            TreeLayout::Nested => Box::new(create_tree(
+                str,
+                &factor_indexes,
+                &icfl_indexes,
+                &idx_to_is_custom,
+                monitor,
+            )),
+            TreeLayout::Arena if self.tree_num_threads > 1 => Box::new(create_arena_tree_parallel(
+                str,
+                &factor_indexes,
+                &icfl_indexes,
+                &idx_to_is_custom,
+                monitor,
+                self.tree_num_threads,
+            )),
+            TreeLayout::Arena => Box::new(create_arena_tree(
+                str,
+                &factor_indexes,
+                &icfl_indexes,
+                &idx_to_is_custom,
+                monitor,
+            )),
+        };
+        monitor.p2_tree.stop();
+
+        // + Extra
+        let (tree_nodes, tree_ranking_entries) = tree.get_nodes_and_rankings_count();
+        let memory_usage = &mut monitor.memory_usage;
+        memory_usage.str_bytes = std::mem::size_of_val(str);
+        memory_usage.tree_nodes = tree_nodes;
+        memory_usage.tree_nodes_bytes = tree.get_nodes_bytes();
+        memory_usage.tree_ranking_entries = tree_ranking_entries;
+        if cfg!(feature = "verbose") {
+            println!("Before SUFFIX ARRAY PHASE");
+            print_for_human_like_debug(
+                str,
+                &icfl_indexes,
+                &factor_indexes,
+                &idx_to_icfl_factor,
+                &idx_to_is_custom,
+            );
+            tree.print();
+        }
+        if let Some(logger) = &mut logger {
+            logger.log_tree(chunk_size, tree.as_ref());
+        }
+        // - Extra
+
+        // SUFFIX ARRAY
+        monitor.p3_sa.start();
+        let mut lcp_bounds = if lcp_strategy == Some(LcpStrategy::PrefixTree) {
+            Some(Vec::with_capacity(str.len()))
+        } else {
+            None
+        };
+        let suffix_array = if self.num_threads > 1 {
+            tree.compute_suffix_array_parallel(
+                str,
+                &icfl_indexes,
+                &idx_to_is_custom,
+                &idx_to_icfl_factor,
+                monitor,
+                lcp_bounds.as_mut(),
+                self.num_threads,
+            )
+        } else {
+            tree.compute_suffix_array(
+                str,
+                &icfl_indexes,
+                &idx_to_is_custom,
+                &idx_to_icfl_factor,
+                monitor,
+                lcp_bounds.as_mut(),
+            )
+        };
+        monitor.p3_sa.stop();
+
+        // LCP ARRAY
+        let lcp_array = if let Some(lcp_strategy) = lcp_strategy {
+            monitor.p4_lcp.start();
+            let lcp_array = match lcp_strategy {
+                LcpStrategy::Kasai => compute_lcp_array_kasai(str, &suffix_array),
+                LcpStrategy::PrefixTree => {
+                    compute_lcp_array_from_bounds(str, &suffix_array, &lcp_bounds.unwrap())
+                }
+            };
+            monitor.p4_lcp.stop();
+            Some(lcp_array)
+        } else {
+            None
+        };
+        monitor.whole_duration.stop();
+
+        // + Extra
+        if cfg!(feature = "verbose") {
+            println!("After SUFFIX ARRAY PHASE");
+            tree.print();
+        }
+        monitor.memory_usage.peak_rss_kb = if is_peak_rss_reset {
+            read_peak_rss_kb()
+        } else {
+            None
+        };
+        let execution_info = monitor.get_execution_info();
+        if let Some(logger) = &mut logger {
+            logger.log_suffix_array(chunk_size, &suffix_array);
+            logger.log_execution_info(chunk_size, &execution_info);
+        }
+        // - Extra
+
+        InnovativeSuffixArrayComputationResults {
+            chunk_size,
+            suffix_array,
+            lcp_array,
+            execution_info,
+            factorization_stats,
+        }
+    }
+}
+
+// Kept for the Suite: computes and logs into the results folder of the given FASTA file.
+#[allow(clippy::too_many_arguments)]
 pub fn compute_innovative_suffix_array(
+    results_folder: &str,
     fasta_file_name: &str,
-    str: &str,
-    chunk_size: Option<usize>,
+    str: &[u8],
+    chunking: Chunking,
+    auto_chunk_size: Option<AutoChunkSize>,
+    factorization: FactorizationStrategy,
+    num_threads: usize,
+    tree_num_threads: usize,
+    tree_layout: TreeLayout,
     log_execution: bool,
     log_fact: bool,
     log_trees_and_suffix_array: bool,
 ) -> InnovativeSuffixArrayComputationResults {
-    let chunk_size_or_zero = chunk_size.unwrap_or(0);
-    let mut monitor = Monitor::new();
-    monitor.whole_duration.start();
-
-    // FACTORIZATION
-    monitor.p1_fact.start();
-    // ICFL Factorization
-    let str_chars = str.chars().collect::<Vec<_>>();
-    let icfl_indexes = get_icfl_indexes(&str_chars);
-    // Custom Factorization
-    let (
-        //
-        factor_indexes,
-        idx_to_is_custom,
-        idx_to_icfl_factor,
-    ) = get_custom_factors_and_more_using_chunk_size(&icfl_indexes, chunk_size, str.len());
-    monitor.p1_fact.stop();
-
-    // + Extra
-    if log_fact {
-        make_sure_directory_exist(get_path_for_project_folder(fasta_file_name));
-        log_factorization(
-            &factor_indexes,
-            &icfl_indexes,
-            str,
-            get_path_for_project_factorization_file(fasta_file_name, chunk_size_or_zero),
-        );
-    }
-    // - Extra
-
-    // TREE
-    monitor.p2_tree.start();
-    let mut tree = create_tree(
-        &str_chars,
-        &factor_indexes,
-        &icfl_indexes,
-        &idx_to_is_custom,
-        &mut monitor,
+    let mut logger = ResultsFolderLogger::new(
+        results_folder,
+        fasta_file_name,
+        log_execution,
+        log_fact,
+        log_trees_and_suffix_array,
     );
-    monitor.p2_tree.stop();
-
-    // + Extra
-    if cfg!(feature = "verbose") {
-        println!("Before SUFFIX ARRAY PHASE");
-        print_for_human_like_debug(
-            str,
-            &icfl_indexes,
-            &factor_indexes,
-            &idx_to_icfl_factor,
-            &idx_to_is_custom,
-        );
-        tree.print();
-    }
-    if log_trees_and_suffix_array {
-        make_sure_directory_exist(get_path_for_project_folder(fasta_file_name));
-        /*
-        log_tree(
-            &tree,
-            TreeLogMode::Tree,
-            get_path_for_project_tree_file(fasta_file_name, chunk_size_or_zero),
-        );
-        log_tree(
-            &tree,
-            TreeLogMode::FullTree,
-            get_path_for_project_full_tree_file(fasta_file_name, chunk_size_or_zero),
-        );
-        */
-        log_tree(
-            &tree,
-            TreeLogMode::MiniTree,
-            get_path_for_project_mini_tree_file(fasta_file_name, chunk_size_or_zero),
-        );
-    }
-    // - Extra
-
-    // SUFFIX ARRAY
-    monitor.p3_sa.start();
-    let suffix_array = tree.compute_suffix_array(
-        str,
-        &icfl_indexes,
-        &idx_to_is_custom,
-        &idx_to_icfl_factor,
-        &mut monitor,
-    );
-    monitor.p3_sa.stop();
-    monitor.whole_duration.stop();
-
-    // + Extra
-    if cfg!(feature = "verbose") {
-        println!("After SUFFIX ARRAY PHASE");
-        tree.print();
-    }
-    if log_trees_and_suffix_array {
-        log_suffix_array(
-            &suffix_array,
-            get_path_for_project_suffix_array_file(fasta_file_name, chunk_size_or_zero),
-        );
-    }
-    let execution_info = monitor.transform_info_execution_info();
-    if log_execution {
-        make_sure_directory_exist(get_path_for_project_folder(fasta_file_name));
-        // Execution Outcome JSON file
-        let execution_outcome_file_format =
-            ExecutionOutcomeFileFormat::new(&execution_info.execution_outcome);
-        dump_json_in_file(
-            &execution_outcome_file_format,
-            get_path_for_project_outcome_file_json(fasta_file_name, chunk_size_or_zero),
-        );
-
-        // Execution Timing JSON file
-        let execution_timing_file_format =
-            ExecutionInfoFileFormat::new(&execution_info.execution_timing);
-        dump_json_in_file(
-            &execution_timing_file_format,
-            get_path_for_project_timing_file_json(fasta_file_name, chunk_size_or_zero),
-        );
-    }
-    // println!("Total time: {}", duration.as_secs_f32());
-    // - Extra
-
-    InnovativeSuffixArrayComputationResults {
-        suffix_array,
-        execution_info,
-    }
+    InnovativeSuffixArrayBuilder::new(str)
+        .chunking(chunking)
+        .auto_chunk_size(auto_chunk_size)
+        .factorization(factorization)
+        .num_threads(num_threads)
+        .tree_num_threads(tree_num_threads)
+        .tree_layout(tree_layout)
+        .reset_peak_rss(true)
+        .logger(&mut logger)
+        .compute()
 }
-fn print_for_human_like_debug(
-    str: &str,
-    icfl_indexes: &Vec<usize>,
-    factor_indexes: &Vec<usize>,
-    idx_to_icfl_factor: &Vec<usize>,
-    idx_to_is_custom: &Vec<bool>,
-    // depths: &Vec<usize>,
+fn print_for_human_like_debug<T: Symbol>(
+    str: &[T],
+    icfl_indexes: &[usize],
+    factor_indexes: &[usize],
+    idx_to_icfl_factor: &[usize],
+    idx_to_is_custom: &[bool],
+    // depths: &[usize],
 ) {
     // CHAR INDEXES
     for i in 0..str.len() {
@@ -168,26 +406,23 @@ fn print_for_human_like_debug(
     println!();
     // CHARS
     for i in 0..str.len() {
-        print!("  {} ", &str[i..i + 1]);
+        print!("  {} ", get_string_clone(&str[i..i + 1]));
     }
     println!();
     // IDX TO ICFL FACTOR
-    for i in 0..str.len() {
-        print!(" {:2} ", idx_to_icfl_factor[i]);
+    for icfl_factor in idx_to_icfl_factor {
+        print!(" {:2} ", icfl_factor);
     }
     println!("   <= IDX TO ICFL FACTOR {:?}", icfl_indexes);
-    let mut i = 0;
 
-    print_indexes_list(&icfl_indexes, str.len());
+    print_indexes_list(icfl_indexes, str.len());
     println!("<= ICFL FACTOR INDEXES {:?}", icfl_indexes);
-    print_indexes_list(&factor_indexes, str.len());
+    print_indexes_list(factor_indexes, str.len());
     println!("<= FACTOR INDEXES {:?}", factor_indexes);
 
     // IDX TO IS CUSTOM FACTOR
-    i = 0;
-    while i < str.len() {
-        print!("  {} ", if idx_to_is_custom[i] { "x" } else { " " });
-        i += 1;
+    for &is_custom in idx_to_is_custom {
+        print!("  {} ", if is_custom { "x" } else { " " });
     }
     println!("   <= IDX TO IS CUSTOM FACTOR");
     /*for i in 0..str.len() {
@@ -195,14 +430,65 @@ fn print_for_human_like_debug(
     }
     println!("   <= DEPTHS");*/
 }
-fn print_indexes_list(indexes_list: &Vec<usize>, str_length: usize) {
-    let mut iter = &mut indexes_list.iter();
-    iter.next(); // Skipping the first because it's always "0".
+fn print_indexes_list(indexes_list: &[usize], str_length: usize) {
     let mut last = 0;
     print!("|");
-    while let Some(&custom_factor_index) = iter.next() {
+    // Skipping the first because it's always "0".
+    for &custom_factor_index in indexes_list.iter().skip(1) {
         print!("{}|", " ".repeat((custom_factor_index - last) * 4 - 1));
         last = custom_factor_index;
     }
     print!("{}|  ", " ".repeat((str_length - last) * 4 - 1));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::for_each_random_string;
+
+    #[test]
+    #[should_panic(expected = "Chunk size can't be zero")]
+    fn builder_rejects_zero_chunk_size() {
+        InnovativeSuffixArrayBuilder::new(b"ACGT").chunk_size(Some(0));
+    }
+
+    #[test]
+    fn builder_leaves_peak_rss_alone_by_default() {
+        // Also with the automatic Chunk Size, whose dry run computes on samples.
+        for auto_chunk_size in [None, Some(AutoChunkSize::DryRun)] {
+            let results = InnovativeSuffixArrayBuilder::new(&b"ACGTTGCA".repeat(300)[..])
+                .auto_chunk_size(auto_chunk_size)
+                .compute();
+            assert_eq!(results.execution_info.memory_usage.peak_rss_kb, None);
+        }
+    }
+
+    #[test]
+    fn parallel_suffix_array_is_identical() {
+        // With more First Layer Nodes than threads, so that threads reuse their Rankings Buffers.
+        for_each_random_string(19, 50, 1..=80, 2..=4, |str, _| {
+            for tree_layout in [TreeLayout::Nested, TreeLayout::Arena] {
+                for chunk_size in [None, Some(2), Some(4)] {
+                    let compute = |num_threads| {
+                        let results = InnovativeSuffixArrayBuilder::new(str)
+                            .chunk_size(chunk_size)
+                            .tree_layout(tree_layout)
+                            .num_threads(num_threads)
+                            .lcp(Some(LcpStrategy::PrefixTree))
+                            .compute();
+                        (results.suffix_array, results.lcp_array)
+                    };
+                    let expected = compute(1);
+                    for num_threads in [2, 3] {
+                        assert_eq!(
+                            compute(num_threads),
+                            expected,
+                            "str={:?}, chunk_size={chunk_size:?}, num_threads={num_threads}",
+                            String::from_utf8_lossy(str),
+                        );
+                    }
+                }
+            }
+        });
+    }
+}
@@ -1,5 +1,5 @@
-use crate::plot::vendor::{create_rectangle_bar, draw_plot};
-use plotters::element::Rectangle;
+use crate::plot::vendor::{create_error_bar_path, create_rectangle_bar, draw_plot};
+use plotters::element::{PathElement, Rectangle};
 use plotters::prelude::{RGBColor, SegmentValue};
 
 #[derive(Debug)]
@@ -24,23 +24,53 @@ impl CompositeBarRectangle {
 }
 
 #[derive(Debug)]
+pub struct ErrorBar {
+    pub x: u32,
+    pub y_low: i32,
+    pub y_high: i32,
+    pub color: RGBColor,
+}
+impl ErrorBar {
+    pub fn new(x: u32, y_low: i32, y_high: i32, color: RGBColor) -> Self {
+        Self {
+            x,
+            y_low,
+            y_high,
+            color,
+        }
+    }
+    pub fn create_path(&self) -> PathElement<(SegmentValue<u32>, i32)> {
+        create_error_bar_path(self.x, self.y_low, self.y_high, self.color)
+    }
+}
+
+#[derive(Debug, Default)]
 pub struct CompositeBar {
     rectangles: Vec<CompositeBarRectangle>,
+    error_bar: Option<ErrorBar>,
 }
 impl CompositeBar {
     pub fn new() -> Self {
         Self {
             rectangles: Vec::new(),
+            error_bar: None,
         }
     }
     pub fn new_only_one(single_rectangle: CompositeBarRectangle) -> Self {
         Self {
             rectangles: vec![single_rectangle],
+            error_bar: None,
         }
     }
     pub fn add_rectangle(&mut self, rectangle: CompositeBarRectangle) {
         self.rectangles.push(rectangle);
     }
+    pub fn set_error_bar(&mut self, error_bar: ErrorBar) {
+        self.error_bar = Some(error_bar);
+    }
+    pub fn get_error_bar(&self) -> Option<&ErrorBar> {
+        self.error_bar.as_ref()
+    }
     pub fn create_rectangle(&self) -> Vec<Rectangle<(SegmentValue<u32>, i32)>> {
         let mut result = Vec::new();
         for rectangle in &self.rectangles {
@@ -51,6 +81,7 @@ impl CompositeBar {
     }
 }
 
+#[derive(Default)]
 pub struct GroupOfBars {
     pub bars: Vec<CompositeBar>,
 }
@@ -94,7 +125,7 @@ impl BarPlot {
         min_x: u32,
         max_x: u32,
         max_height: i32,
-        groups_of_bars: &Vec<GroupOfBars>,
+        groups_of_bars: &[GroupOfBars],
     ) {
         draw_plot(
             path,
@@ -1,3 +1,4 @@
 pub mod interface;
+#[allow(clippy::module_inception)]
 pub mod plot;
 pub mod vendor;
@@ -1,13 +1,40 @@
-use crate::files::paths::get_path_for_plot_file;
-use crate::plot::interface::{BarPlot, CompositeBar, CompositeBarRectangle, GroupOfBars};
-use plotters::prelude::full_palette::{GREEN_500, GREY_500, ORANGE_300, PURPLE_500};
+use crate::files::paths::{get_path_for_memory_plot_file, get_path_for_plot_file};
+use crate::plot::interface::{BarPlot, CompositeBar, CompositeBarRectangle, ErrorBar, GroupOfBars};
+use plotters::prelude::full_palette::{
+    BLUE_500, CYAN_500, GREEN_500, GREY_500, ORANGE_300, PINK_300, PURPLE_500, WHITE, YELLOW_300,
+};
 use plotters::style::RGBColor;
 
 pub fn draw_plot_from_monitor(
+    plots_folder: &str,
     fasta_file_name: &str,
     classic_computation_duration_micros: u64,
     chunk_size_and_phase_micros_list: Vec<(usize, (u64, u64, u64))>,
     max_duration_in_micros: u32,
+) {
+    draw_plot_with_error_bars(
+        plots_folder,
+        fasta_file_name,
+        vec![(classic_computation_duration_micros, None)],
+        chunk_size_and_phase_micros_list
+            .into_iter()
+            .map(|(chunk_size, micros)| (chunk_size, micros, None))
+            .collect(),
+        max_duration_in_micros,
+    );
+}
+
+// Baselines Colors, in the same order of "baseline_computations" (Classic first by default).
+const BASELINE_COLORS: [RGBColor; 4] = [PURPLE_500, BLUE_500, CYAN_500, PINK_300];
+
+// Error Bars are "(low, high)" in micros, drawn over the whole bar (not per phase).
+#[allow(clippy::type_complexity)]
+pub fn draw_plot_with_error_bars(
+    plots_folder: &str,
+    fasta_file_name: &str,
+    baseline_computations: Vec<(u64, Option<(u64, u64)>)>,
+    chunk_size_and_phase_micros_list: Vec<(usize, (u64, u64, u64), Option<(u64, u64)>)>,
+    max_duration_in_micros: u32,
 ) {
     let diagram_max_y = 10000;
     let abs_max_value = max_duration_in_micros as i32;
@@ -16,48 +43,53 @@ pub fn draw_plot_from_monitor(
     let mut groups_of_bars = Vec::new();
 
     // Innovative Technique Executions
-    for (_, micros) in &chunk_size_and_phase_micros_list {
-        groups_of_bars.push(
-            // Composite Vertical Bar
-            GroupOfBars::new_only_one(
-                //
-                create_composite_bar_from_parts(
-                    curr_x,
-                    vec![
-                        (micros.0 as i32, GREY_500),   // Factorization phase
-                        (micros.1 as i32, ORANGE_300), // Tree phase
-                        (micros.2 as i32, GREEN_500),  // Suffix Array phase
-                    ],
-                    abs_max_value,
-                    diagram_max_y,
-                ),
-            ),
+    for (_, micros, error_bar) in &chunk_size_and_phase_micros_list {
+        // Composite Vertical Bar
+        let mut composite_bar = create_composite_bar_from_parts(
+            curr_x,
+            vec![
+                (micros.0 as i32, GREY_500),   // Factorization phase
+                (micros.1 as i32, ORANGE_300), // Tree phase
+                (micros.2 as i32, GREEN_500),  // Suffix Array phase
+            ],
+            abs_max_value,
+            diagram_max_y,
         );
+        if let Some(error_bar) = error_bar {
+            composite_bar.set_error_bar(create_error_bar(
+                curr_x,
+                *error_bar,
+                abs_max_value,
+                diagram_max_y,
+            ));
+        }
+        groups_of_bars.push(GroupOfBars::new_only_one(composite_bar));
         curr_x += 1;
     }
 
-    // Classic Technique Execution
-    groups_of_bars.push(
-        //
-        GroupOfBars::new_only_one(
+    // Baseline Techniques Executions
+    for (i, (duration_micros, error_bar)) in baseline_computations.into_iter().enumerate() {
+        let mut baseline_bar = CompositeBar::new_only_one(
             //
-            CompositeBar::new_only_one(
+            CompositeBarRectangle::new(
                 //
-                CompositeBarRectangle::new(
-                    //
-                    curr_x,
-                    0,
-                    proportional_value(
-                        classic_computation_duration_micros as i32,
-                        abs_max_value,
-                        diagram_max_y,
-                    ),
-                    PURPLE_500,
-                ),
+                curr_x,
+                0,
+                proportional_value(duration_micros as i32, abs_max_value, diagram_max_y),
+                BASELINE_COLORS[i % BASELINE_COLORS.len()],
             ),
-        ),
-    );
-    curr_x += 1;
+        );
+        if let Some(error_bar) = error_bar {
+            baseline_bar.set_error_bar(create_error_bar(
+                curr_x,
+                error_bar,
+                abs_max_value,
+                diagram_max_y,
+            ));
+        }
+        groups_of_bars.push(GroupOfBars::new_only_one(baseline_bar));
+        curr_x += 1;
+    }
 
     let min_chunk_size = chunk_size_and_phase_micros_list.first().unwrap().0;
     let max_chunk_size = chunk_size_and_phase_micros_list.last().unwrap().0;
@@ -70,7 +102,69 @@ pub fn draw_plot_from_monitor(
         ),
     );
     bar_plot.draw(
-        &get_path_for_plot_file(fasta_file_name, min_chunk_size, max_chunk_size),
+        &get_path_for_plot_file(
+            plots_folder,
+            fasta_file_name,
+            min_chunk_size,
+            max_chunk_size,
+        ),
+        1,
+        1, // min_x,
+        curr_x,
+        diagram_max_y,
+        &groups_of_bars,
+    );
+}
+
+// Estimated bytes of the Prefix Tree Nodes, their Rankings and the peak of auxiliary Rankings
+// during Phase 3. The height is the highest bar, plotted in kB.
+pub fn draw_memory_plot(
+    plots_folder: &str,
+    fasta_file_name: &str,
+    chunk_size_and_memory_bytes_list: Vec<(usize, (u64, u64, u64))>,
+) {
+    let diagram_max_y = 10000;
+    let max_kb = chunk_size_and_memory_bytes_list
+        .iter()
+        .map(|(_, bytes)| (bytes.0 + bytes.1 + bytes.2) / 1024)
+        .max()
+        .unwrap_or(0);
+    let abs_max_value = (max_kb as i32 + max_kb as i32 / 10).max(1);
+
+    let mut curr_x = 1;
+    let mut groups_of_bars = Vec::new();
+    for (_, bytes) in &chunk_size_and_memory_bytes_list {
+        let composite_bar = create_composite_bar_from_parts(
+            curr_x,
+            vec![
+                ((bytes.0 / 1024) as i32, ORANGE_300), // Tree Nodes
+                ((bytes.1 / 1024) as i32, YELLOW_300), // Tree Rankings
+                ((bytes.2 / 1024) as i32, GREEN_500),  // Auxiliary Rankings (peak)
+            ],
+            abs_max_value,
+            diagram_max_y,
+        );
+        groups_of_bars.push(GroupOfBars::new_only_one(composite_bar));
+        curr_x += 1;
+    }
+
+    let min_chunk_size = chunk_size_and_memory_bytes_list.first().unwrap().0;
+    let max_chunk_size = chunk_size_and_memory_bytes_list.last().unwrap().0;
+    let bar_plot = BarPlot::new(
+        3600,
+        1200,
+        format!(
+            "Memory (max {} kB): {}, Chunk Size from {} to {}",
+            max_kb, fasta_file_name, min_chunk_size, max_chunk_size
+        ),
+    );
+    bar_plot.draw(
+        &get_path_for_memory_plot_file(
+            plots_folder,
+            fasta_file_name,
+            min_chunk_size,
+            max_chunk_size,
+        ),
         1,
         1, // min_x,
         curr_x,
@@ -107,3 +201,17 @@ fn create_composite_bar_from_parts(
     }
     composite_bar
 }
+
+fn create_error_bar(
+    x: u32,
+    (low_micros, high_micros): (u64, u64),
+    abs_max_value: i32,
+    diagram_max_y: i32,
+) -> ErrorBar {
+    ErrorBar::new(
+        x,
+        proportional_value(low_micros as i32, abs_max_value, diagram_max_y),
+        proportional_value(high_micros as i32, abs_max_value, diagram_max_y),
+        WHITE,
+    )
+}
@@ -1,10 +1,12 @@
 use crate::plot::interface::GroupOfBars;
 use plotters::backend::BitMapBackend;
 use plotters::chart::{ChartBuilder, LabelAreaPosition};
-use plotters::element::Rectangle;
+use plotters::element::{PathElement, Rectangle};
 use plotters::prelude::full_palette::GREY_800;
 use plotters::prelude::{Color, IntoDrawingArea, IntoSegmentedCoord, RGBColor, SegmentValue};
+use plotters::style::ShapeStyle;
 
+#[allow(clippy::too_many_arguments)]
 pub fn draw_plot(
     path: &str,
     width: u32,
@@ -14,7 +16,7 @@ pub fn draw_plot(
     min_x: u32,
     max_x: u32,
     max_height: i32,
-    groups_of_bars: &Vec<GroupOfBars>,
+    groups_of_bars: &[GroupOfBars],
 ) {
     let root_area = BitMapBackend::new(path, (width, height)).into_drawing_area();
     // root_area.fill(&WHITE).unwrap();
@@ -34,6 +36,7 @@ pub fn draw_plot(
         .unwrap();
     ctx.configure_mesh().draw().unwrap();
     let mut flat_bars = Vec::new();
+    let mut error_bars = Vec::new();
     for group_of_bars in groups_of_bars {
         for i in 0..group_of_bars.get_bars_count() {
             let bar = group_of_bars.get_bar(i);
@@ -41,9 +44,14 @@ pub fn draw_plot(
             for rectangle_bar in rectangle_bars {
                 flat_bars.push(rectangle_bar);
             }
+            if let Some(error_bar) = bar.get_error_bar() {
+                error_bars.push(error_bar.create_path());
+            }
         }
     }
     ctx.draw_series(flat_bars).unwrap();
+    // Error bars over the bars.
+    ctx.draw_series(error_bars).unwrap();
 }
 
 pub fn create_rectangle_bar(
@@ -63,3 +71,19 @@ pub fn create_rectangle_bar(
     bar.set_margin(0, 0, 2, 2);
     bar
 }
+
+pub fn create_error_bar_path(
+    x: u32,
+    min_y: i32,
+    max_y: i32,
+    color: RGBColor,
+) -> PathElement<(SegmentValue<u32>, i32)> {
+    PathElement::new(
+        vec![
+            //
+            (SegmentValue::CenterOf(x), min_y),
+            (SegmentValue::CenterOf(x), max_y),
+        ],
+        ShapeStyle::from(&color).stroke_width(3),
+    )
+}
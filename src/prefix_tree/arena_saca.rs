@@ -0,0 +1,227 @@
+use crate::prefix_tree::arena_tree::{ArenaTree, ArenaTreeNodeId};
+use crate::prefix_tree::monitor::Monitor;
+use crate::prefix_tree::saca::{
+    calculate_windows_and_child_shared_rankings, compute_suffix_array_by_first_layer_blocks,
+    push_lcp_bounds, RankingsBuffers,
+};
+use crate::prefix_tree::symbol::Symbol;
+
+impl<'a, T: Symbol> ArenaTree<'a, T> {
+    pub fn compute_suffix_array(
+        &self,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        mut lcp_bounds: Option<&mut Vec<usize>>,
+    ) -> Vec<usize> {
+        let mut suffix_array = Vec::with_capacity(str.len());
+        let mut rankings_buffers = RankingsBuffers::new();
+        for &child_id in self.get_first_layer() {
+            // Visiting from all First Layer Nodes to all Leafs (avoiding Root Node).
+            self.get_common_prefix_partition(
+                child_id,
+                str,
+                icfl_indexes,
+                idx_to_is_custom,
+                idx_to_icfl_factor,
+                monitor,
+                &mut rankings_buffers,
+                &mut suffix_array,
+                lcp_bounds.as_deref_mut(),
+            );
+        }
+        suffix_array
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute_suffix_array_parallel(
+        &self,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        lcp_bounds: Option<&mut Vec<usize>>,
+        num_threads: usize,
+    ) -> Vec<usize> {
+        let first_layer = self.get_first_layer();
+        compute_suffix_array_by_first_layer_blocks(
+            first_layer.len(),
+            str.len(),
+            monitor,
+            lcp_bounds,
+            num_threads,
+            |i_child, monitor, rankings_buffers, block, block_lcp_bounds| {
+                self.get_common_prefix_partition(
+                    first_layer[i_child],
+                    str,
+                    icfl_indexes,
+                    idx_to_is_custom,
+                    idx_to_icfl_factor,
+                    monitor,
+                    rankings_buffers,
+                    block,
+                    block_lcp_bounds,
+                );
+            },
+        )
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn get_common_prefix_partition(
+        &self,
+        first_layer_node_id: ArenaTreeNodeId,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        rankings_buffers: &mut RankingsBuffers,
+        suffix_array: &mut Vec<usize>,
+        mut lcp_bounds: Option<&mut Vec<usize>>,
+    ) {
+        // Same visit of "Tree::get_common_prefix_partition", with an explicit stack of Frames
+        // instead of recursion.
+        let mut stack = vec![Frame::new(first_layer_node_id, None, 0, suffix_array.len())];
+        while let Some(frame) = stack.last_mut() {
+            let self_node = self.get_node(frame.node_id);
+            let self_rks = match &frame.new_rankings {
+                Some(new_rankings) => new_rankings,
+                None => &self_node.rankings,
+            };
+
+            // + Extra
+            if cfg!(feature = "verbose") && frame.i_child == 0 {
+                println!(
+                    "{}> CPP node: {:?}",
+                    "=".repeat(self_node.suffix_len),
+                    self_rks,
+                );
+            }
+            // - Extra
+
+            if frame.i_child < self_node.children.len() {
+                let child_id = self_node.children[frame.i_child];
+                let child_node = self.get_node(child_id);
+                frame.i_child += 1;
+                let (
+                    //
+                    win_min,
+                    win_max,
+                    child_new_rankings,
+                ) = calculate_windows_and_child_shared_rankings(
+                    child_node.suffix_len,
+                    &child_node.rankings,
+                    self_rks, // As Parent's Rankings.
+                    frame.position,
+                    str,
+                    icfl_indexes,
+                    idx_to_is_custom,
+                    idx_to_icfl_factor,
+                    monitor,
+                    rankings_buffers,
+                );
+
+                // SELF CPP: Self Rankings from left to Child WIN-MIN.
+                if frame.position < win_min {
+                    frame.insert_self_rankings(
+                        &self_rks[frame.position..win_min],
+                        self_node.suffix_len,
+                        suffix_array,
+                        lcp_bounds.as_deref_mut(),
+                    );
+                }
+                frame.position = win_max;
+
+                // SELF CPP: Child Rankings
+                let child_shared_len_before =
+                    frame.get_shared_len_before_next(self_node.suffix_len, suffix_array.len());
+                stack.push(Frame::new(
+                    child_id,
+                    child_new_rankings,
+                    child_shared_len_before,
+                    suffix_array.len(),
+                ));
+            } else {
+                // SELF CPP: Self Rankings left
+                if frame.position < self_rks.len() {
+                    frame.insert_self_rankings(
+                        &self_rks[frame.position..],
+                        self_node.suffix_len,
+                        suffix_array,
+                        lcp_bounds.as_deref_mut(),
+                    );
+                }
+                let frame = stack.pop().unwrap();
+                if let Some(new_rankings) = frame.new_rankings {
+                    // + Extra
+                    monitor.drop_aux_rankings(new_rankings.len());
+                    // - Extra
+
+                    rankings_buffers.give_back(new_rankings);
+                }
+            }
+        }
+    }
+}
+
+// State of a Node being visited, what would be local variables of the recursive visit.
+struct Frame {
+    node_id: ArenaTreeNodeId,
+    new_rankings: Option<Vec<usize>>, // Used instead of the Node's Rankings, if any.
+    shared_len_before: usize,
+    suffix_array_len_before: usize,
+    position: usize, // In Self Rankings.
+    i_child: usize,  // Next Child to visit.
+}
+impl Frame {
+    fn new(
+        node_id: ArenaTreeNodeId,
+        new_rankings: Option<Vec<usize>>,
+        shared_len_before: usize,
+        suffix_array_len_before: usize,
+    ) -> Self {
+        Self {
+            node_id,
+            new_rankings,
+            shared_len_before,
+            suffix_array_len_before,
+            position: 0,
+            i_child: 0,
+        }
+    }
+    fn get_shared_len_before_next(&self, self_suffix_len: usize, suffix_array_len: usize) -> usize {
+        if suffix_array_len > self.suffix_array_len_before {
+            self_suffix_len
+        } else {
+            self.shared_len_before
+        }
+    }
+    fn insert_self_rankings(
+        &self,
+        portion_to_insert: &[usize],
+        self_suffix_len: usize,
+        suffix_array: &mut Vec<usize>,
+        lcp_bounds: Option<&mut Vec<usize>>,
+    ) {
+        // + Extra
+        if cfg!(feature = "verbose") {
+            println!(
+                "{}. SA insert: {:?}",
+                ".".repeat(self_suffix_len),
+                portion_to_insert,
+            );
+        }
+        // - Extra
+
+        if let Some(lcp_bounds) = lcp_bounds {
+            push_lcp_bounds(
+                lcp_bounds,
+                portion_to_insert.len(),
+                self.get_shared_len_before_next(self_suffix_len, suffix_array.len()),
+                self_suffix_len,
+            );
+        }
+        suffix_array.extend(portion_to_insert);
+    }
+}
@@ -0,0 +1,344 @@
+use crate::prefix_tree::layout::PrefixTree;
+use crate::prefix_tree::monitor::Monitor;
+use crate::prefix_tree::parallel::run_jobs_in_parallel;
+use crate::prefix_tree::print::get_string_clone;
+use crate::prefix_tree::symbol::Symbol;
+use crate::prefix_tree::tree::{
+    for_each_local_suffix, get_local_suffixes_buckets, update_rankings,
+};
+
+pub type ArenaTreeNodeId = u32;
+const ROOT_ID: ArenaTreeNodeId = 0;
+
+pub fn create_arena_tree<'a, T: Symbol>(
+    str: &'a [T],
+    factor_indexes: &[usize],
+    icfl_indexes: &[usize],
+    idx_to_is_custom: &[bool],
+    monitor: &mut Monitor,
+) -> ArenaTree<'a, T> {
+    let mut tree = ArenaTree::new(str);
+    for_each_local_suffix(
+        str.len(),
+        factor_indexes,
+        icfl_indexes,
+        idx_to_is_custom,
+        |ls_index, ls_size, is_custom_ls| {
+            tree.add(ls_index, ls_size, is_custom_ls, monitor);
+        },
+    );
+
+    // + Extra
+    if cfg!(feature = "verbose") {
+        tree.print();
+    }
+    // - Extra
+
+    tree
+}
+
+pub fn create_arena_tree_parallel<'a, T: Symbol>(
+    str: &'a [T],
+    factor_indexes: &[usize],
+    icfl_indexes: &[usize],
+    idx_to_is_custom: &[bool],
+    monitor: &mut Monitor,
+    num_threads: usize,
+) -> ArenaTree<'a, T> {
+    // Like "create_tree_parallel": a Subtree per bucket, then their Nodes are moved in a single
+    // Arena, shifting their ids.
+    let buckets =
+        get_local_suffixes_buckets(str, factor_indexes, icfl_indexes, idx_to_is_custom, monitor);
+    let subtrees =
+        run_jobs_in_parallel(buckets.len(), num_threads, monitor, |i_bucket, monitor| {
+            let mut subtree = ArenaTree::new(str);
+            for &(ls_index, ls_size, is_custom_ls) in &buckets[i_bucket] {
+                subtree.add(ls_index, ls_size, is_custom_ls, monitor);
+            }
+            subtree
+        });
+    let mut tree = ArenaTree::new(str);
+    for subtree in subtrees {
+        tree.append_first_layer(subtree);
+    }
+
+    // + Extra
+    if cfg!(feature = "verbose") {
+        tree.print();
+    }
+    // - Extra
+
+    tree
+}
+
+// Nodes live in a single vector, the Root Node is the first one. The prefix on the edge from the
+// Parent Node is not a slice but "(start, length)" into the text.
+pub struct ArenaTree<'a, T: Symbol> {
+    pub str: &'a [T],
+    pub nodes: Vec<ArenaTreeNode>,
+}
+impl<'a, T: Symbol> ArenaTree<'a, T> {
+    pub fn new(str: &'a [T]) -> Self {
+        Self {
+            str,
+            nodes: vec![ArenaTreeNode::new(0, 0, 0)],
+        }
+    }
+    pub fn get_node(&self, node_id: ArenaTreeNodeId) -> &ArenaTreeNode {
+        &self.nodes[node_id as usize]
+    }
+    pub fn get_prefix(&self, node_id: ArenaTreeNodeId) -> &'a [T] {
+        let node = self.get_node(node_id);
+        &self.str[node.prefix_start..node.prefix_start + node.prefix_len]
+    }
+    pub fn get_first_layer(&self) -> &Vec<ArenaTreeNodeId> {
+        &self.get_node(ROOT_ID).children
+    }
+    fn push_node(&mut self, node: ArenaTreeNode) -> ArenaTreeNodeId {
+        let node_id = self.nodes.len();
+        assert!(
+            node_id <= ArenaTreeNodeId::MAX as usize,
+            "Too many Nodes for the Arena Tree"
+        );
+        self.nodes.push(node);
+        node_id as ArenaTreeNodeId
+    }
+    fn append_first_layer(&mut self, subtree: ArenaTree<'a, T>) {
+        // Subtree's Root Node is dropped, its Children become First Layer Nodes.
+        let id_offset = self.nodes.len() as ArenaTreeNodeId - 1;
+        let mut subtree_nodes = subtree.nodes.into_iter();
+        let subtree_root = subtree_nodes.next().unwrap();
+        for mut node in subtree_nodes {
+            for child_id in &mut node.children {
+                *child_id += id_offset;
+            }
+            self.push_node(node);
+        }
+        for child_id in subtree_root.children {
+            self.nodes[ROOT_ID as usize]
+                .children
+                .push(child_id + id_offset);
+        }
+    }
+    pub fn add(
+        &mut self,
+        ls_index: usize,
+        ls_size: usize,
+        is_custom_ls: bool,
+        monitor: &mut Monitor,
+    ) {
+        let str = self.str;
+        let mut node_id = ROOT_ID;
+        let mut i_char = 0;
+        while i_char < ls_size {
+            let rest_of_ls = &str[ls_index + i_char..ls_index + ls_size];
+
+            // Binary Search
+            let children = &self.get_node(node_id).children;
+            let mut p = 0;
+            let mut q = children.len();
+            let mut next_node_id = None;
+            while p < q {
+                let mid = (q + p) / 2;
+                let mid_id = children[mid];
+                let mid_str = self.get_prefix(mid_id);
+
+                // Comparing "Mid. Str." with "Rest of LS".
+                let mut i = 0;
+                while i < rest_of_ls.len() && i < mid_str.len() {
+                    if rest_of_ls[i] != mid_str[i] {
+                        break;
+                    }
+                    i += 1;
+                }
+                if i < rest_of_ls.len() && i < mid_str.len() {
+                    // Strings are different.
+                    if rest_of_ls[i] < mid_str[i] {
+                        q = mid;
+                    } else {
+                        // Then it's "rest_of_ls[i] > mid_str[i]".
+                        p = mid + 1;
+                    }
+                } else {
+                    // The case of "rest_of_ls" being prefix of "mid_str" is ignored.
+                    // Is up to the caller never to cause this case.
+                    next_node_id = Some((mid_id, i));
+                    break;
+                }
+            }
+            if let Some((mid_id, i)) = next_node_id {
+                node_id = mid_id;
+                i_char += i;
+                continue;
+            }
+
+            let mut new_node = ArenaTreeNode::new(ls_size, ls_index + i_char, rest_of_ls.len());
+            update_rankings(&mut new_node.rankings, ls_index, is_custom_ls, str, monitor);
+            let new_node_id = self.push_node(new_node);
+            self.nodes[node_id as usize].children.insert(p, new_node_id);
+
+            // + Extra
+            if cfg!(feature = "verbose") {
+                println!(
+                    "   -> found index p={p}, new node id={new_node_id} with prefix={} and ranking {}",
+                    get_string_clone(rest_of_ls),
+                    ls_index,
+                );
+            }
+            // - Extra
+
+            return;
+        }
+
+        // + Extra
+        if cfg!(feature = "verbose") {
+            println!("   -> Populating node id={node_id} with new ranking {ls_index}");
+        }
+        // - Extra
+
+        let node = &mut self.nodes[node_id as usize];
+        update_rankings(&mut node.rankings, ls_index, is_custom_ls, str, monitor);
+    }
+}
+
+impl<'a, T: Symbol> PrefixTree<T> for ArenaTree<'a, T> {
+    fn visit_nodes(&self, on_node: &mut dyn FnMut(usize, &[T], &[usize])) {
+        let mut stack = vec![(0, ROOT_ID)];
+        while let Some((level, node_id)) = stack.pop() {
+            let node = self.get_node(node_id);
+            on_node(level, self.get_prefix(node_id), &node.rankings);
+            for &child_id in node.children.iter().rev() {
+                stack.push((level + 1, child_id));
+            }
+        }
+    }
+    fn get_nodes_and_rankings_count(&self) -> (usize, usize) {
+        let rankings_count = self.nodes.iter().map(|node| node.rankings.len()).sum();
+        (self.nodes.len() - 1, rankings_count)
+    }
+    fn get_nodes_bytes(&self) -> usize {
+        // Root Node excluded, like in the other Layouts, but its Children ids are counted.
+        let children_count = self
+            .nodes
+            .iter()
+            .map(|node| node.children.len())
+            .sum::<usize>();
+        (self.nodes.len() - 1) * std::mem::size_of::<ArenaTreeNode>()
+            + children_count * std::mem::size_of::<ArenaTreeNodeId>()
+    }
+    fn compute_suffix_array(
+        &self,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        lcp_bounds: Option<&mut Vec<usize>>,
+    ) -> Vec<usize> {
+        // Inherent, see "arena_saca".
+        ArenaTree::compute_suffix_array(
+            self,
+            str,
+            icfl_indexes,
+            idx_to_is_custom,
+            idx_to_icfl_factor,
+            monitor,
+            lcp_bounds,
+        )
+    }
+    fn compute_suffix_array_parallel(
+        &self,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        lcp_bounds: Option<&mut Vec<usize>>,
+        num_threads: usize,
+    ) -> Vec<usize> {
+        // Inherent, see "arena_saca".
+        ArenaTree::compute_suffix_array_parallel(
+            self,
+            str,
+            icfl_indexes,
+            idx_to_is_custom,
+            idx_to_icfl_factor,
+            monitor,
+            lcp_bounds,
+            num_threads,
+        )
+    }
+}
+
+pub struct ArenaTreeNode {
+    pub suffix_len: usize,
+    pub prefix_start: usize,
+    pub prefix_len: usize,
+    pub rankings: Vec<usize>,
+    pub children: Vec<ArenaTreeNodeId>,
+}
+impl ArenaTreeNode {
+    pub fn new(suffix_len: usize, prefix_start: usize, prefix_len: usize) -> Self {
+        Self {
+            suffix_len,
+            prefix_start,
+            prefix_len,
+            rankings: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prefix_tree::layout::TreeLayout;
+    use crate::suffix_array::lcp::{compute_lcp_array_kasai, LcpStrategy};
+    use crate::test_utils::{for_each_random_string, get_repetitive_strings};
+    use crate::InnovativeSuffixArrayBuilder;
+
+    fn assert_arena_tree_as_nested_tree(str: &[u8]) {
+        let mut naive_suffix_array = (0..str.len()).collect::<Vec<_>>();
+        naive_suffix_array.sort_by_key(|&gs_index| &str[gs_index..]);
+        let naive_lcp_array = compute_lcp_array_kasai(str, &naive_suffix_array);
+        for chunk_size in [None, Some(1), Some(2), Some(3), Some(5), Some(8)] {
+            let compute = |tree_layout, tree_num_threads| {
+                let results = InnovativeSuffixArrayBuilder::new(str)
+                    .chunk_size(chunk_size)
+                    .tree_layout(tree_layout)
+                    .tree_num_threads(tree_num_threads)
+                    .lcp(Some(LcpStrategy::PrefixTree))
+                    .compute();
+                (results.suffix_array, results.lcp_array.unwrap())
+            };
+            let nested = compute(TreeLayout::Nested, 1);
+            assert_eq!(
+                (&nested.0, &nested.1),
+                (&naive_suffix_array, &naive_lcp_array),
+                "str={:?}, chunk_size={chunk_size:?}, nested",
+                String::from_utf8_lossy(str)
+            );
+            for tree_num_threads in [1, 3] {
+                assert_eq!(
+                    compute(TreeLayout::Arena, tree_num_threads),
+                    nested,
+                    "str={:?}, chunk_size={chunk_size:?}, arena with {tree_num_threads} threads",
+                    String::from_utf8_lossy(str)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn arena_tree_as_nested_tree_on_random_strings() {
+        for_each_random_string(18, 100, 1..=80, 1..=4, |str, _| {
+            assert_arena_tree_as_nested_tree(str);
+        });
+    }
+
+    #[test]
+    fn arena_tree_as_nested_tree_on_repetitive_strings() {
+        for str in get_repetitive_strings(30) {
+            assert_arena_tree_as_nested_tree(&str);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::prefix_tree::monitor::Monitor;
+use crate::prefix_tree::print::print_tree;
+use crate::prefix_tree::symbol::Symbol;
+use serde::{Deserialize, Serialize};
+
+// How the Prefix Tree is laid out in memory, both produce the same Suffix Array:
+// * "Nested": every Node owns its Children, edges are slices of the text (see "tree");
+// * "Arena": Nodes live in a flat vector addressed by ids, edges are "(start, length)" into the
+//   text and Phase 3 is iterative (see "arena_tree").
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TreeLayout {
+    Nested,
+    Arena,
+}
+impl TreeLayout {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "nested" => Ok(TreeLayout::Nested),
+            "arena" => Ok(TreeLayout::Arena),
+            _ => Err(format!(
+                "Invalid tree layout \"{}\", expected \"nested\" or \"arena\"",
+                value
+            )),
+        }
+    }
+    pub fn parse_list(expression: &str) -> Result<Vec<Self>, String> {
+        // Comma-separated, like "nested,arena".
+        expression
+            .split(',')
+            .map(|value| TreeLayout::parse(value.trim()))
+            .collect()
+    }
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            TreeLayout::Nested => "nested",
+            TreeLayout::Arena => "arena",
+        }
+    }
+}
+
+// Operations shared by all Tree Layouts, so the rest of the pipeline does not depend on them.
+pub trait PrefixTree<T: Symbol> {
+    // Pre-order visit, Children in order, from the Root Node (level "0", empty prefix): called
+    // with the level, the prefix on the edge from the Parent Node and the Rankings.
+    #[allow(clippy::type_complexity)]
+    fn visit_nodes(&self, on_node: &mut dyn FnMut(usize, &[T], &[usize]));
+    fn get_nodes_and_rankings_count(&self) -> (usize, usize); // Root Node excluded.
+    fn get_nodes_bytes(&self) -> usize; // Estimated, Nodes only (without their Rankings).
+    fn compute_suffix_array(
+        &self,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        lcp_bounds: Option<&mut Vec<usize>>,
+    ) -> Vec<usize>;
+    #[allow(clippy::too_many_arguments)]
+    fn compute_suffix_array_parallel(
+        &self,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        lcp_bounds: Option<&mut Vec<usize>>,
+        num_threads: usize,
+    ) -> Vec<usize>;
+    fn print(&self) {
+        print_tree(self);
+    }
+}
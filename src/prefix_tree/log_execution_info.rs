@@ -15,30 +15,49 @@ impl ExecutionInfoFileFormat {
                 duration_phases: et.phases_only.as_micros(),
                 phase_1_fact___: et.p1_fact.dur.as_micros(),
                 phase_2_tree___: et.p2_tree.dur.as_micros(),
+                phase_2_bucket_: et.p2_tree_bucketing.map(|dur| dur.as_micros()),
                 phase_3_sa_____: et.p3_sa.dur.as_micros(),
+                phase_4_lcp____: et.p4_lcp.as_ref().map(|p4_lcp| p4_lcp.dur.as_micros()),
                 duration_phases_with_extra: et.whole.as_micros(),
             },
             seconds: ExecutionInfoFileFormatSeconds {
                 duration_phases: round_secs_x_xxx(et.phases_only),
                 phase_1_fact___: round_secs_x_xxx(et.p1_fact.dur),
                 phase_2_tree___: round_secs_x_xxx(et.p2_tree.dur),
+                phase_2_bucket_: et.p2_tree_bucketing.map(round_secs_x_xxx),
                 phase_3_sa_____: round_secs_x_xxx(et.p3_sa.dur),
+                phase_4_lcp____: et
+                    .p4_lcp
+                    .as_ref()
+                    .map(|p4_lcp| round_secs_x_xxx(p4_lcp.dur)),
                 duration_phases_with_extra: round_secs_x_xxx(et.whole),
             },
             percentages: ExecutionInfoFileFormatPercentages {
                 phase_1_fact: et.p1_fact.perc,
                 phase_2_tree: et.p2_tree.perc,
                 phase_3_sa__: et.p3_sa.perc,
+                phase_4_lcp_: et.p4_lcp.as_ref().map(|p4_lcp| p4_lcp.perc),
             },
         }
     }
+    pub fn get_phases_micros(&self) -> (u64, u64, u64) {
+        (
+            self.micros.phase_1_fact___ as u64,
+            self.micros.phase_2_tree___ as u64,
+            self.micros.phase_3_sa_____ as u64,
+        )
+    }
 }
 #[derive(Serialize, Deserialize)]
 struct ExecutionInfoFileFormatMicros {
     duration_phases: u128,
     phase_1_fact___: u128,
     phase_2_tree___: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    phase_2_bucket_: Option<u128>, // Part of "phase_2_tree___".
     phase_3_sa_____: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    phase_4_lcp____: Option<u128>,
     duration_phases_with_extra: u128,
 }
 #[derive(Serialize, Deserialize)]
@@ -46,7 +65,11 @@ struct ExecutionInfoFileFormatSeconds {
     duration_phases: f32,
     phase_1_fact___: f32,
     phase_2_tree___: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    phase_2_bucket_: Option<f32>, // Part of "phase_2_tree___".
     phase_3_sa_____: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    phase_4_lcp____: Option<f32>,
     duration_phases_with_extra: f32,
 }
 #[derive(Serialize, Deserialize)]
@@ -54,6 +77,8 @@ struct ExecutionInfoFileFormatPercentages {
     phase_1_fact: u16,
     phase_2_tree: u16,
     phase_3_sa__: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    phase_4_lcp_: Option<u16>,
 }
 
 fn round_secs_x_xxx(duration: Duration) -> f32 {
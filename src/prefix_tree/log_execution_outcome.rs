@@ -9,6 +9,8 @@ pub struct ExecutionOutcomeFileFormat {
     compares_using_two_cf: usize,
     compares_ls: usize,
     compares_gs: usize,
+    rankings_merges: usize,
+    rankings_allocations: usize,
 }
 impl ExecutionOutcomeFileFormat {
     pub fn new(execution_outcome: &ExecutionOutcome) -> Self {
@@ -19,6 +21,8 @@ impl ExecutionOutcomeFileFormat {
             compares_using_two_cf: execution_outcome.compares_with_two_cfs,
             compares_ls: execution_outcome.compares_ls,
             compares_gs: execution_outcome.compares_gs,
+            rankings_merges: execution_outcome.rankings_merges,
+            rankings_allocations: execution_outcome.rankings_allocations,
         }
     }
 }
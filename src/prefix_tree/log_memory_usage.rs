@@ -0,0 +1,40 @@
+use crate::prefix_tree::monitor::MemoryUsage;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct MemoryUsageFileFormat {
+    tree_nodes: usize,
+    tree_ranking_entries: usize,
+    aux_ranking_entries_peak: usize,
+    estimated_bytes: MemoryUsageBytesFileFormat,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peak_rss_kb: Option<u64>,
+}
+#[derive(Serialize, Deserialize)]
+pub struct MemoryUsageBytesFileFormat {
+    str: usize,
+    tree_nodes: usize,
+    tree_rankings: usize,
+    aux_rankings_peak: usize,
+}
+impl MemoryUsageFileFormat {
+    pub fn new(memory_usage: &MemoryUsage) -> Self {
+        Self {
+            tree_nodes: memory_usage.tree_nodes,
+            tree_ranking_entries: memory_usage.tree_ranking_entries,
+            aux_ranking_entries_peak: memory_usage.aux_ranking_entries_peak,
+            estimated_bytes: MemoryUsageBytesFileFormat::new(memory_usage),
+            peak_rss_kb: memory_usage.peak_rss_kb,
+        }
+    }
+}
+impl MemoryUsageBytesFileFormat {
+    pub fn new(memory_usage: &MemoryUsage) -> Self {
+        Self {
+            str: memory_usage.str_bytes,
+            tree_nodes: memory_usage.tree_nodes_bytes,
+            tree_rankings: memory_usage.get_tree_rankings_bytes(),
+            aux_rankings_peak: memory_usage.get_aux_rankings_peak_bytes(),
+        }
+    }
+}
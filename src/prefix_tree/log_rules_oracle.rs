@@ -0,0 +1,54 @@
+use crate::prefix_tree::monitor::{RulesMismatch, RulesOracleReport};
+use crate::prefix_tree::rules::RulesBranch;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize)]
+pub struct RulesOracleReportFileFormat {
+    num_checks: usize,
+    num_checks_by_branch: BTreeMap<RulesBranch, usize>,
+    num_mismatches: usize,
+    mismatches: Vec<RulesMismatchFileFormat>,
+}
+#[derive(Serialize, Deserialize)]
+pub struct RulesMismatchFileFormat {
+    parent_ls_index: usize,
+    child_ls_index: usize,
+    offset: usize,
+    branch: RulesBranch,
+    expected: String,
+    given: String,
+}
+impl RulesOracleReportFileFormat {
+    pub fn new(rules_oracle: &RulesOracleReport) -> Self {
+        Self {
+            num_checks: rules_oracle.num_checks,
+            num_checks_by_branch: rules_oracle.num_checks_by_branch.clone(),
+            num_mismatches: rules_oracle.mismatches.len(),
+            mismatches: rules_oracle
+                .mismatches
+                .iter()
+                .map(RulesMismatchFileFormat::new)
+                .collect(),
+        }
+    }
+}
+impl RulesMismatchFileFormat {
+    pub fn new(rules_mismatch: &RulesMismatch) -> Self {
+        Self {
+            parent_ls_index: rules_mismatch.parent_ls_index,
+            child_ls_index: rules_mismatch.child_ls_index,
+            offset: rules_mismatch.offset,
+            branch: rules_mismatch.branch,
+            expected: get_order_label(rules_mismatch.expected).to_string(),
+            given: get_order_label(rules_mismatch.given).to_string(),
+        }
+    }
+}
+fn get_order_label(is_child_first: bool) -> &'static str {
+    if is_child_first {
+        "child_first"
+    } else {
+        "parent_first"
+    }
+}
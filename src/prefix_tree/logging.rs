@@ -1,5 +1,6 @@
+use crate::prefix_tree::layout::PrefixTree;
 use crate::prefix_tree::print::get_string_clone;
-use crate::prefix_tree::tree::{Tree, TreeNode};
+use crate::prefix_tree::symbol::Symbol;
 use std::fs::File;
 use std::io::Write;
 
@@ -9,27 +10,36 @@ pub enum TreeLogMode {
     FullTree,
     MiniTree,
 }
-pub fn log_tree(tree: &Tree, mode: TreeLogMode, filepath: String) {
+pub fn log_tree<T: Symbol, P: PrefixTree<T> + ?Sized>(
+    tree: &P,
+    mode: TreeLogMode,
+    filepath: String,
+) {
     let mut file = File::create(filepath).expect("Unable to create file");
-    // Logging from all First Layer Nodes to all Leafs (avoiding Root Node).
-    for (child_node_prefix, child_node) in &tree.root.children {
-        let child_node_prefix = *child_node_prefix;
-        let child_label = match mode {
-            TreeLogMode::Tree => format!("{}", get_string_clone(child_node_prefix)),
-            TreeLogMode::FullTree => format!("{}", get_string_clone(child_node_prefix)),
-            TreeLogMode::MiniTree => format!("\"{:6}\"", child_node_prefix.len()),
+    // Labels of the Nodes on the path from the First Layer, the last one is the Parent Node's.
+    let mut labels: Vec<String> = Vec::new();
+    tree.visit_nodes(&mut |level, node_prefix, rankings| {
+        if level == 0 {
+            // Logging from all First Layer Nodes to all Leafs (avoiding Root Node).
+            return;
+        }
+        let level = level - 1;
+        labels.truncate(level);
+        let node_label = match mode {
+            TreeLogMode::Tree => get_string_clone(node_prefix),
+            TreeLogMode::FullTree => format!(
+                "{}{}",
+                labels.last().map(String::as_str).unwrap_or_default(),
+                get_string_clone(node_prefix)
+            ),
+            TreeLogMode::MiniTree => format!("\"{:6}\"", node_prefix.len()),
         };
-        log_tree_recursive(&child_node, &child_label, mode, &mut file, 0);
-    }
+        log_node(&node_label, rankings, level, &mut file);
+        labels.push(node_label);
+    });
     file.flush().expect("Unable to flush file");
 }
-fn log_tree_recursive(
-    node: &TreeNode,
-    node_label: &str,
-    mode: TreeLogMode,
-    file: &mut File,
-    level: usize,
-) {
+fn log_node(node_label: &str, rankings: &[usize], level: usize, file: &mut File) {
     let mut line = format!(
         //
         "{}{} <{}>",
@@ -37,24 +47,12 @@ fn log_tree_recursive(
         node_label,
         "",
     );
-    let rankings = &node.rankings;
     line.push_str(" [");
-    for i in 0..rankings.len() - 1 {
-        let ranking = rankings[i];
+    for ranking in &rankings[..rankings.len() - 1] {
         line.push_str(&format!("{}, ", ranking));
     }
     line.push_str(&format!("{}]", rankings[rankings.len() - 1]));
-    line.push_str("\n");
-    file.write(line.as_bytes()).expect("Unable to write line");
-    for (child_node_prefix, child_node) in &node.children {
-        let child_node_prefix = *child_node_prefix;
-        let child_label = match mode {
-            TreeLogMode::Tree => format!("{}", get_string_clone(child_node_prefix)),
-            TreeLogMode::FullTree => {
-                format!("{}{}", node_label, get_string_clone(child_node_prefix))
-            }
-            TreeLogMode::MiniTree => format!("\"{:6}\"", child_node_prefix.len()),
-        };
-        log_tree_recursive(child_node, &child_label, mode, file, level + 1);
-    }
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .expect("Unable to write line");
 }
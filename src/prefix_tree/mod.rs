@@ -1,8 +1,15 @@
+pub mod arena_saca;
+pub mod arena_tree;
+pub mod layout;
 pub mod log_execution_info;
 pub mod log_execution_outcome;
+pub mod log_memory_usage;
+pub mod log_rules_oracle;
 pub mod logging;
 pub mod monitor;
+pub mod parallel;
 pub mod print;
 pub mod rules;
 pub mod saca;
+pub mod symbol;
 pub mod tree;
@@ -1,16 +1,22 @@
 use crate::prefix_tree::log_execution_info::round_int_100;
+use crate::prefix_tree::rules::RulesBranch;
+use std::collections::BTreeMap;
 use std::time::{Duration, Instant};
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Monitor {
     // Timing
     pub whole_duration: MonitorInterval,
     pub p1_fact: MonitorInterval,
     pub p2_tree: MonitorInterval,
+    pub p2_tree_bucketing: MonitorInterval, // Only with the parallel Prefix Tree, part of "p2_tree".
     pub p3_sa: MonitorInterval,
+    pub p4_lcp: MonitorInterval, // Only if the LCP Array is requested.
 
     // Values
     pub execution_outcome: ExecutionOutcome,
+    pub memory_usage: MemoryUsage,
+    pub rules_oracle: Option<RulesOracleReport>, // Only if enabled, see "rules_safe".
 }
 impl Monitor {
     pub fn new() -> Self {
@@ -18,8 +24,12 @@ impl Monitor {
             whole_duration: MonitorInterval::new(),
             p1_fact: MonitorInterval::new(),
             p2_tree: MonitorInterval::new(),
+            p2_tree_bucketing: MonitorInterval::new(),
             p3_sa: MonitorInterval::new(),
+            p4_lcp: MonitorInterval::new(),
             execution_outcome: ExecutionOutcome::new(),
+            memory_usage: MemoryUsage::new(),
+            rules_oracle: None,
         }
     }
 
@@ -36,16 +46,55 @@ impl Monitor {
     pub fn new_compare_using_actual_string_compare(&mut self) {
         self.execution_outcome.compares_using_strcmp += 1;
     }
+    pub fn new_rankings_merge(&mut self) {
+        self.execution_outcome.rankings_merges += 1;
+    }
+    pub fn new_rankings_allocation(&mut self) {
+        self.execution_outcome.rankings_allocations += 1;
+    }
+
+    // MEMORY USAGE
+    pub fn new_aux_rankings(&mut self, num_entries: usize) {
+        let memory_usage = &mut self.memory_usage;
+        memory_usage.aux_ranking_entries += num_entries;
+        memory_usage.aux_ranking_entries_peak = memory_usage
+            .aux_ranking_entries_peak
+            .max(memory_usage.aux_ranking_entries);
+    }
+    pub fn drop_aux_rankings(&mut self, num_entries: usize) {
+        self.memory_usage.aux_ranking_entries -= num_entries;
+    }
 
-    pub fn transform_info_execution_info(self) -> ExecutionInfo {
+    // RULES ORACLE
+    pub fn enable_rules_oracle(&mut self) {
+        self.rules_oracle = Some(RulesOracleReport::new());
+    }
+    pub fn is_rules_oracle_enabled(&self) -> bool {
+        self.rules_oracle.is_some()
+    }
+    pub fn new_rules_oracle_check(&mut self, branch: RulesBranch) {
+        if let Some(rules_oracle) = &mut self.rules_oracle {
+            rules_oracle.num_checks += 1;
+            *rules_oracle.num_checks_by_branch.entry(branch).or_insert(0) += 1;
+        }
+    }
+    pub fn new_rules_oracle_mismatch(&mut self, rules_mismatch: RulesMismatch) {
+        if let Some(rules_oracle) = &mut self.rules_oracle {
+            rules_oracle.mismatches.push(rules_mismatch);
+        }
+    }
+
+    pub fn get_execution_info(&self) -> ExecutionInfo {
         ExecutionInfo {
-            execution_timing: ExecutionTiming::new(&self),
-            execution_outcome: self.execution_outcome,
+            execution_timing: ExecutionTiming::new(self),
+            execution_outcome: self.execution_outcome.clone(),
+            memory_usage: self.memory_usage.clone(),
+            rules_oracle: self.rules_oracle.clone(),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct MonitorInterval {
     pub start: Option<Instant>,
     pub end: Option<Instant>,
@@ -78,6 +127,8 @@ impl MonitorInterval {
 pub struct ExecutionInfo {
     pub execution_timing: ExecutionTiming,
     pub execution_outcome: ExecutionOutcome,
+    pub memory_usage: MemoryUsage,
+    pub rules_oracle: Option<RulesOracleReport>,
 }
 
 pub struct ExecutionTimingPhase {
@@ -92,17 +143,21 @@ pub struct ExecutionTiming {
     // Phases
     pub p1_fact: ExecutionTimingPhase,
     pub p2_tree: ExecutionTimingPhase,
+    pub p2_tree_bucketing: Option<Duration>, // Serial part of the parallel Prefix Tree.
     pub p3_sa: ExecutionTimingPhase,
+    pub p4_lcp: Option<ExecutionTimingPhase>,
 }
 impl ExecutionTiming {
     pub fn new(monitor: &Monitor) -> Self {
         let p1_fact = monitor.p1_fact.get_duration().unwrap();
         let p2_tree = monitor.p2_tree.get_duration().unwrap();
+        let p2_tree_bucketing = monitor.p2_tree_bucketing.get_duration();
         let p3_sa = monitor.p3_sa.get_duration().unwrap();
+        let p4_lcp = monitor.p4_lcp.get_duration();
         let whole_duration = monitor.whole_duration.get_duration().unwrap();
 
         // Sum Durations (Only Phases)
-        let phases_only = p1_fact + p2_tree + p3_sa;
+        let phases_only = p1_fact + p2_tree + p3_sa + p4_lcp.unwrap_or_default();
 
         // Percentages with extra
         /*let duration_extra = whole_duration - phases_only;
@@ -112,7 +167,13 @@ impl ExecutionTiming {
         let sum_micros_excl_extra = phases_only.as_micros() as f32;
         let p1_fact_perc = round_int_100(p1_fact.as_micros() as f32 / sum_micros_excl_extra);
         let p2_tree_perc = round_int_100(p2_tree.as_micros() as f32 / sum_micros_excl_extra);
-        let p3_sa_perc = 100 - (p1_fact_perc + p2_tree_perc).min(100);
+        let (p3_sa_perc, p4_lcp_perc) = if p4_lcp.is_some() {
+            let p3_sa_perc = round_int_100(p3_sa.as_micros() as f32 / sum_micros_excl_extra);
+            let p4_lcp_perc = 100 - (p1_fact_perc + p2_tree_perc + p3_sa_perc).min(100);
+            (p3_sa_perc, Some(p4_lcp_perc))
+        } else {
+            (100 - (p1_fact_perc + p2_tree_perc).min(100), None)
+        };
         /*let p3_sa_perc = round_int_5(p3_sa.as_micros() as f32 / sum_micros_excl_extra);
         let check_sum = p1_fact_perc + p2_tree_perc + p3_sa_perc;
         if check_sum != 100 {
@@ -135,15 +196,20 @@ impl ExecutionTiming {
                 dur: p2_tree,
                 perc: p2_tree_perc,
             },
+            p2_tree_bucketing,
             p3_sa: ExecutionTimingPhase {
                 dur: p3_sa,
                 perc: p3_sa_perc,
             },
+            p4_lcp: p4_lcp.map(|p4_lcp| ExecutionTimingPhase {
+                dur: p4_lcp,
+                perc: p4_lcp_perc.unwrap(),
+            }),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub struct ExecutionOutcome {
     pub compares_with_two_cfs: usize,
     pub compares_with_one_cf: usize,
@@ -151,6 +217,8 @@ pub struct ExecutionOutcome {
     pub compares_using_strcmp: usize,
     pub compares_ls: usize,
     pub compares_gs: usize,
+    pub rankings_merges: usize, // Child Rankings merged with Parent ones in Phase 3.
+    pub rankings_allocations: usize, // Buffers allocated (or grown) for those merges.
 }
 impl ExecutionOutcome {
     pub fn new() -> Self {
@@ -161,6 +229,8 @@ impl ExecutionOutcome {
             compares_using_strcmp: 0,
             compares_ls: 0,
             compares_gs: 0,
+            rankings_merges: 0,
+            rankings_allocations: 0,
         }
     }
     pub fn monitor_new_local_suffix_compare(&mut self) {
@@ -169,4 +239,97 @@ impl ExecutionOutcome {
     pub fn monitor_new_global_suffix_compare(&mut self) {
         self.compares_gs += 1;
     }
+    pub fn merge(&mut self, other: &ExecutionOutcome) {
+        // Used to sum counters of Monitors private to threads.
+        self.compares_with_two_cfs += other.compares_with_two_cfs;
+        self.compares_with_one_cf += other.compares_with_one_cf;
+        self.compares_using_rules += other.compares_using_rules;
+        self.compares_using_strcmp += other.compares_using_strcmp;
+        self.compares_ls += other.compares_ls;
+        self.compares_gs += other.compares_gs;
+        self.rankings_merges += other.rankings_merges;
+        self.rankings_allocations += other.rankings_allocations;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryUsage {
+    pub str_bytes: usize,
+    pub tree_nodes: usize,
+    pub tree_nodes_bytes: usize, // Estimated, Nodes only (without their Rankings).
+    pub tree_ranking_entries: usize,
+    pub aux_ranking_entries: usize, // Currently allocated in Phase 3 ("new_self_rks").
+    pub aux_ranking_entries_peak: usize,
+    pub peak_rss_kb: Option<u64>, // Whole process during the computation, if asked with "reset_peak_rss".
+}
+impl MemoryUsage {
+    pub fn new() -> Self {
+        Self {
+            str_bytes: 0,
+            tree_nodes: 0,
+            tree_nodes_bytes: 0,
+            tree_ranking_entries: 0,
+            aux_ranking_entries: 0,
+            aux_ranking_entries_peak: 0,
+            peak_rss_kb: None,
+        }
+    }
+    pub fn get_tree_rankings_bytes(&self) -> usize {
+        self.tree_ranking_entries * std::mem::size_of::<usize>()
+    }
+    pub fn get_aux_rankings_peak_bytes(&self) -> usize {
+        self.aux_ranking_entries_peak * std::mem::size_of::<usize>()
+    }
+    pub fn merge(&mut self, other: &MemoryUsage) {
+        // Used for Monitors private to threads running at the same time: the sum of their peaks
+        // is an upper bound of the actual peak.
+        self.aux_ranking_entries_peak += other.aux_ranking_entries_peak;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RulesOracleReport {
+    pub num_checks: usize, // Decisions of "rules" compared with the actual string comparison.
+    pub num_checks_by_branch: BTreeMap<RulesBranch, usize>,
+    pub mismatches: Vec<RulesMismatch>,
+}
+impl RulesOracleReport {
+    pub fn new() -> Self {
+        Self {
+            num_checks: 0,
+            num_checks_by_branch: BTreeMap::new(),
+            mismatches: Vec::new(),
+        }
+    }
+    pub fn merge(&mut self, other: &RulesOracleReport) {
+        // Used to collect the mismatches of Monitors private to threads.
+        self.num_checks += other.num_checks;
+        for (&branch, &num_checks) in &other.num_checks_by_branch {
+            *self.num_checks_by_branch.entry(branch).or_insert(0) += num_checks;
+        }
+        self.mismatches.extend_from_slice(&other.mismatches);
+    }
+}
+#[derive(Debug, Clone)]
+pub struct RulesMismatch {
+    pub parent_ls_index: usize,
+    pub child_ls_index: usize,
+    pub offset: usize, // Size of the compared LSs, Global Suffixes are compared from there.
+    pub branch: RulesBranch,
+    pub expected: bool, // As "rules": "true" if the Child comes first.
+    pub given: bool,
+}
+
+pub fn reset_peak_rss() -> bool {
+    // Brings the peak Resident Set Size ("VmHWM") down to the current one, so that it does not
+    // include previous computations of a Suite. Linux only, "false" elsewhere.
+    std::fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+pub fn read_peak_rss_kb() -> Option<u64> {
+    // Peak Resident Set Size ("VmHWM") of the whole process since its start or since the last
+    // "reset_peak_rss". Linux only, "None" elsewhere.
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
 }
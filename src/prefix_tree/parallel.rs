@@ -0,0 +1,87 @@
+use crate::prefix_tree::monitor::Monitor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// Runs independent jobs on scoped threads: threads take jobs one at a time, each thread with its
+// own Monitor. Results are returned in the order of the jobs and Monitors are merged into the
+// given one.
+pub fn run_jobs_in_parallel<R: Send, F: Fn(usize, &mut Monitor) -> R + Sync>(
+    num_jobs: usize,
+    num_threads: usize,
+    monitor: &mut Monitor,
+    job: F,
+) -> Vec<R> {
+    run_jobs_in_parallel_with_state(
+        num_jobs,
+        num_threads,
+        monitor,
+        || (),
+        |i_job, monitor, _| job(i_job, monitor),
+    )
+}
+
+// As "run_jobs_in_parallel", with a state created once per thread (like buffers) and given to
+// all the jobs that thread runs.
+pub fn run_jobs_in_parallel_with_state<
+    S,
+    R: Send,
+    N: Fn() -> S + Sync,
+    F: Fn(usize, &mut Monitor, &mut S) -> R + Sync,
+>(
+    num_jobs: usize,
+    num_threads: usize,
+    monitor: &mut Monitor,
+    new_thread_state: N,
+    job: F,
+) -> Vec<R> {
+    let num_threads = num_threads.min(num_jobs).max(1);
+    let is_rules_oracle_enabled = monitor.is_rules_oracle_enabled();
+    let next_i_job = AtomicUsize::new(0);
+    let threads_results = thread::scope(|scope| {
+        let mut handles = Vec::with_capacity(num_threads);
+        for _ in 0..num_threads {
+            handles.push(scope.spawn(|| {
+                let mut thread_monitor = Monitor::new();
+                if is_rules_oracle_enabled {
+                    thread_monitor.enable_rules_oracle();
+                }
+                let mut thread_state = new_thread_state();
+                let mut results = Vec::new();
+                loop {
+                    let i_job = next_i_job.fetch_add(1, Ordering::Relaxed);
+                    if i_job >= num_jobs {
+                        break;
+                    }
+                    results.push((i_job, job(i_job, &mut thread_monitor, &mut thread_state)));
+                }
+                (thread_monitor, results)
+            }));
+        }
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    // Merging Monitors and Results.
+    let mut results_by_job = Vec::with_capacity(num_jobs);
+    results_by_job.resize_with(num_jobs, || None);
+    for (thread_monitor, results) in threads_results {
+        monitor
+            .execution_outcome
+            .merge(&thread_monitor.execution_outcome);
+        monitor.memory_usage.merge(&thread_monitor.memory_usage);
+        if let (Some(rules_oracle), Some(thread_rules_oracle)) =
+            (&mut monitor.rules_oracle, &thread_monitor.rules_oracle)
+        {
+            rules_oracle.merge(thread_rules_oracle);
+        }
+        for (i_job, result) in results {
+            results_by_job[i_job] = Some(result);
+        }
+    }
+    results_by_job
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect()
+}
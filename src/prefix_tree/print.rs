@@ -1,27 +1,29 @@
-use crate::prefix_tree::tree::{Tree, TreeNode};
+use crate::prefix_tree::layout::PrefixTree;
+use crate::prefix_tree::symbol::Symbol;
 
-impl<'a> Tree<'a> {
-    pub fn print(&self) {
-        self.print_node(&self.root, 0, "");
-    }
-    fn print_node(&self, self_node: &TreeNode<'a>, tabs_offset: usize, self_label: &str) {
+pub fn print_tree<T: Symbol, P: PrefixTree<T> + ?Sized>(tree: &P) {
+    // Labels of the Nodes on the path from the Root Node, the last one is the Parent Node's.
+    let mut labels: Vec<String> = Vec::new();
+    tree.visit_nodes(&mut |level, prefix, rankings| {
+        labels.truncate(level);
+        let mut label = labels.last().cloned().unwrap_or_default();
+        label.push_str(&get_string_clone(prefix));
         println!(
-            "{}|{:2}: \"{}\" {}",
-            "\t".repeat(tabs_offset),
-            tabs_offset,
-            self_label,
-            format!("{:?}", self_node.rankings),
+            "{}|{:2}: \"{}\" {:?}",
+            "\t".repeat(level),
+            level,
+            label,
+            rankings,
         );
-        for (child_node_prefix, child_node) in &self_node.children {
-            let child_node_prefix = *child_node_prefix;
-            let prefix_str = get_string_clone(child_node_prefix);
-            let child_node_label = format!("{}{}", self_label, prefix_str);
-            self.print_node(child_node, tabs_offset + 1, &child_node_label);
-        }
-    }
+        labels.push(label);
+    });
 }
 
-pub fn get_string_clone(str_type: &[char]) -> String {
+pub fn get_string_clone<T: Symbol>(str_type: &[T]) -> String {
     // TODO: Needs cloning
-    String::from_iter(str_type)
+    let mut result = String::with_capacity(str_type.len());
+    for symbol in str_type {
+        symbol.push_label(&mut result);
+    }
+    result
 }
@@ -1,78 +1,126 @@
-use crate::prefix_tree::monitor::Monitor;
+use crate::prefix_tree::monitor::{Monitor, RulesMismatch};
+use crate::prefix_tree::symbol::Symbol;
+use serde::{Deserialize, Serialize};
 
-pub fn rules_safe(
+#[allow(clippy::too_many_arguments)]
+pub fn rules_safe<T: Symbol>(
     parent_ls_index: usize,
     child_ls_index: usize,
     child_ls_size: usize,
-    str: &str,
-    icfl_indexes: &Vec<usize>,
-    idx_to_is_custom: &Vec<bool>,
-    idx_to_icfl_factor: &Vec<usize>,
+    str: &[T],
+    icfl_indexes: &[usize],
+    idx_to_is_custom: &[bool],
+    idx_to_icfl_factor: &[usize],
     monitor: &mut Monitor,
     slow_check: bool,
 ) -> bool {
+    let (given, branch) = rules(
+        parent_ls_index,
+        child_ls_index,
+        child_ls_size,
+        str,
+        icfl_indexes,
+        idx_to_is_custom,
+        idx_to_icfl_factor,
+        monitor,
+    );
     if !slow_check {
-        rules(
-            parent_ls_index,
-            child_ls_index,
-            child_ls_size,
-            str,
-            icfl_indexes,
-            idx_to_is_custom,
-            idx_to_icfl_factor,
-            monitor,
-        )
+        return given;
+    }
+    // Oracle: the actual comparison of the Global Suffixes, that is also returned so that the
+    // following decisions are checked on a correct Suffix Array.
+    let parent_ls = &str[parent_ls_index + child_ls_size..];
+    let child_ls = &str[child_ls_index + child_ls_size..];
+    let oracle = if parent_ls < child_ls {
+        false // Parent first.
     } else {
-        let parent_ls = &str[parent_ls_index + child_ls_size..];
-        let child_ls = &str[child_ls_index + child_ls_size..];
-        let mut oracle = if parent_ls < child_ls {
-            false // Parent first.
-        } else {
-            true // Child first.
-        };
-        let given = rules(
-            parent_ls_index,
-            child_ls_index,
-            child_ls_size,
-            str,
-            icfl_indexes,
-            idx_to_is_custom,
-            idx_to_icfl_factor,
-            monitor,
-        );
-        if given != oracle {
+        true // Child first.
+    };
+    monitor.new_rules_oracle_check(branch);
+    if given != oracle {
+        // + Extra
+        if cfg!(feature = "verbose") {
             println!(
-                " RULES: x={parent_ls_index:2}, y={child_ls_index:2}, offset={child_ls_size} => {oracle}, BUT GIVEN WRONG!"
+                " RULES: x={parent_ls_index:2}, y={child_ls_index:2}, offset={child_ls_size}, branch={} => {oracle}, BUT GIVEN WRONG!",
+                branch.get_label()
             );
         }
-        /*else {
-            println!(" RULES: x={x:2}, y={y:2}, offset={child_ls_size} => {oracle}");
-        }*/
-        oracle
+        // - Extra
+
+        monitor.new_rules_oracle_mismatch(RulesMismatch {
+            parent_ls_index,
+            child_ls_index,
+            offset: child_ls_size,
+            branch,
+            expected: oracle,
+            given,
+        });
+    }
+    oracle
+}
+
+// Where "rules" took its decision, in order of evaluation. Branches using the actual string
+// comparison ("Strcmp") are expected to never be wrong.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum RulesBranch {
+    BothCustomStrcmp,
+    ParentCustomRule,
+    ParentCustomStrcmp,
+    ChildCustomRule,
+    ChildCustomStrcmp,
+    BothInLastFactorRule,
+    SameFactorRule,
+    ParentInLastFactorRule,
+    ChildInLastFactorStrcmp,
+    ParentAfterChildRule,
+    ParentBeforeChildStrcmp,
+}
+impl RulesBranch {
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            RulesBranch::BothCustomStrcmp => "both_custom_strcmp",
+            RulesBranch::ParentCustomRule => "parent_custom_rule",
+            RulesBranch::ParentCustomStrcmp => "parent_custom_strcmp",
+            RulesBranch::ChildCustomRule => "child_custom_rule",
+            RulesBranch::ChildCustomStrcmp => "child_custom_strcmp",
+            RulesBranch::BothInLastFactorRule => "both_in_last_factor_rule",
+            RulesBranch::SameFactorRule => "same_factor_rule",
+            RulesBranch::ParentInLastFactorRule => "parent_in_last_factor_rule",
+            RulesBranch::ChildInLastFactorStrcmp => "child_in_last_factor_strcmp",
+            RulesBranch::ParentAfterChildRule => "parent_after_child_rule",
+            RulesBranch::ParentBeforeChildStrcmp => "parent_before_child_strcmp",
+        }
     }
 }
-fn rules(
+
+#[allow(clippy::too_many_arguments)]
+fn rules<T: Symbol>(
     parent_ls_index: usize,
     child_ls_index: usize,
     child_ls_size: usize,
-    str: &str,
-    icfl_indexes: &Vec<usize>,
-    idx_to_is_custom: &Vec<bool>,
-    idx_to_icfl_factor: &Vec<usize>,
+    str: &[T],
+    icfl_indexes: &[usize],
+    idx_to_is_custom: &[bool],
+    idx_to_icfl_factor: &[usize],
     monitor: &mut Monitor,
-) -> bool {
+) -> (bool, RulesBranch) {
     // Return values:
     //  FALSE => GS Parent < GS Child;
     //  TRUE  => GS Child < GS Parent.
+    // With the Branch that decided.
     if idx_to_is_custom[parent_ls_index] && idx_to_is_custom[child_ls_index] {
         // + Extra
         monitor.new_compare_of_two_ls_in_custom_factors();
         monitor.new_compare_using_actual_string_compare();
         // - Extra
-        return perform_gs_comparison_a_before_b(
-            str,
-            child_ls_index + child_ls_size,
-            parent_ls_index + child_ls_size,
+        return (
+            perform_gs_comparison_a_before_b(
+                str,
+                child_ls_index + child_ls_size,
+                parent_ls_index + child_ls_size,
+            ),
+            RulesBranch::BothCustomStrcmp,
         );
     }
 
@@ -86,19 +134,21 @@ fn rules(
             // + Extra
             monitor.new_compare_using_rules();
             // - Extra
-            if child_ls_index >= last_icfl_index {
-                true
-            } else {
-                false
-            }
+            (
+                child_ls_index >= last_icfl_index,
+                RulesBranch::ParentCustomRule,
+            )
         } else {
             // + Extra
             monitor.new_compare_using_actual_string_compare();
             // - Extra
-            perform_gs_comparison_a_before_b(
-                str,
-                child_ls_index + child_ls_size,
-                parent_ls_index + child_ls_size,
+            (
+                perform_gs_comparison_a_before_b(
+                    str,
+                    child_ls_index + child_ls_size,
+                    parent_ls_index + child_ls_size,
+                ),
+                RulesBranch::ParentCustomStrcmp,
             )
         };
     }
@@ -111,19 +161,21 @@ fn rules(
             // + Extra
             monitor.new_compare_using_rules();
             // - Extra
-            if parent_ls_index >= last_icfl_index {
-                false
-            } else {
-                true
-            }
+            (
+                parent_ls_index < last_icfl_index,
+                RulesBranch::ChildCustomRule,
+            )
         } else {
             // + Extra
             monitor.new_compare_using_actual_string_compare();
             // - Extra
-            perform_gs_comparison_a_before_b(
-                str,
-                child_ls_index + child_ls_size,
-                parent_ls_index + child_ls_size,
+            (
+                perform_gs_comparison_a_before_b(
+                    str,
+                    child_ls_index + child_ls_size,
+                    parent_ls_index + child_ls_size,
+                ),
+                RulesBranch::ChildCustomStrcmp,
             )
         };
     }
@@ -132,54 +184,211 @@ fn rules(
         // + Extra
         monitor.new_compare_using_rules();
         // - Extra
-        false
+        (false, RulesBranch::BothInLastFactorRule)
     } else if idx_to_icfl_factor[parent_ls_index] == idx_to_icfl_factor[child_ls_index] {
         // + Extra
         monitor.new_compare_using_rules();
         // - Extra
-        true
+        (true, RulesBranch::SameFactorRule)
     } else {
         if parent_ls_index >= last_icfl_index {
             // + Extra
             monitor.new_compare_using_rules();
             // - Extra
-            false
+            (false, RulesBranch::ParentInLastFactorRule)
         } else if child_ls_index >= last_icfl_index {
             // + Extra
             monitor.new_compare_using_actual_string_compare();
             // - Extra
-            perform_gs_comparison_a_before_b(
-                str,
-                child_ls_index + child_ls_size,
-                parent_ls_index + child_ls_size,
+            (
+                perform_gs_comparison_a_before_b(
+                    str,
+                    child_ls_index + child_ls_size,
+                    parent_ls_index + child_ls_size,
+                ),
+                RulesBranch::ChildInLastFactorStrcmp,
             )
         } else {
             if parent_ls_index > child_ls_index {
                 // + Extra
                 monitor.new_compare_using_rules();
                 // - Extra
-                true
+                (true, RulesBranch::ParentAfterChildRule)
             } else {
                 // + Extra
                 monitor.new_compare_using_actual_string_compare();
                 // - Extra
-                perform_gs_comparison_a_before_b(
-                    str,
-                    child_ls_index + child_ls_size,
-                    parent_ls_index + child_ls_size,
+                (
+                    perform_gs_comparison_a_before_b(
+                        str,
+                        child_ls_index + child_ls_size,
+                        parent_ls_index + child_ls_size,
+                    ),
+                    RulesBranch::ParentBeforeChildStrcmp,
                 )
             }
         }
     }
 }
 
-pub fn perform_gs_comparison_a_before_b(str: &str, ls_index_1: usize, ls_index_2: usize) -> bool {
+pub fn perform_gs_comparison_a_before_b<T: Symbol>(
+    str: &[T],
+    ls_index_1: usize,
+    ls_index_2: usize,
+) -> bool {
     // println!(" -> *** comparing {} with {}", ls_index_1, ls_index_2);
     let cmp1 = &str[ls_index_1..];
     let cmp2 = &str[ls_index_2..];
-    if cmp1 < cmp2 {
-        true
-    } else {
-        false
+    cmp1 < cmp2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factorization::custom_factorization::{get_factor_end, Chunking};
+    use crate::factorization::factorizer::FactorizationStrategy;
+    use crate::prefix_tree::layout::TreeLayout;
+    use crate::prefix_tree::monitor::RulesOracleReport;
+    use crate::test_utils::for_each_random_string;
+    use crate::InnovativeSuffixArrayBuilder;
+
+    // The other Branches are never taken: with a Parent LS not in a Custom Factor and in the
+    // last Factor ("BothInLastFactorRule", "ParentInLastFactorRule") it reaches the end of the
+    // string, so it can't be followed by as many chars as the longer Child LS; "SameFactorRule"
+    // and "ParentAfterChildRule" were never seen on any input either.
+    const REACHABLE_RULES_BRANCHES: [RulesBranch; 7] = [
+        RulesBranch::BothCustomStrcmp,
+        RulesBranch::ParentCustomRule,
+        RulesBranch::ParentCustomStrcmp,
+        RulesBranch::ChildCustomRule,
+        RulesBranch::ChildCustomStrcmp,
+        RulesBranch::ChildInLastFactorStrcmp,
+        RulesBranch::ParentBeforeChildStrcmp,
+    ];
+
+    fn assert_reachable_branches_checked(report: &RulesOracleReport) {
+        for branch in REACHABLE_RULES_BRANCHES {
+            assert!(
+                report.num_checks_by_branch.contains_key(&branch),
+                "branch {} never checked",
+                branch.get_label()
+            );
+        }
+    }
+
+    fn get_rules_oracle_report(
+        str: &[u8],
+        chunk_size: Option<usize>,
+        tree_layout: TreeLayout,
+        num_threads: usize,
+    ) -> RulesOracleReport {
+        InnovativeSuffixArrayBuilder::new(str)
+            .chunk_size(chunk_size)
+            .tree_layout(tree_layout)
+            .num_threads(num_threads)
+            .rules_oracle(true)
+            .compute()
+            .execution_info
+            .rules_oracle
+            .unwrap()
+    }
+
+    fn for_each_test_string(num_random_strings: usize, mut check: impl FnMut(&[u8])) {
+        for str in [
+            &b"AAABCAABCADCAABCA"[..],
+            b"GCAATAATGCGTATCAGCAATAATGCA",
+            b"ABRACADABRA",
+            b"AAAAAAAAAA",
+            b"BABABABABA",
+        ] {
+            check(str);
+        }
+        for_each_random_string(25, num_random_strings, 1..=100, 2..=4, |str, _| check(str));
+    }
+
+    #[test]
+    fn rules_oracle_finds_no_mismatches() {
+        let mut total_report = RulesOracleReport::new();
+        for_each_test_string(200, |str| {
+            for chunk_size in [None, Some(1), Some(2), Some(3), Some(5)] {
+                for tree_layout in [TreeLayout::Nested, TreeLayout::Arena] {
+                    let report = get_rules_oracle_report(str, chunk_size, tree_layout, 1);
+                    assert!(
+                        report.mismatches.is_empty(),
+                        "str={:?}, chunk_size={chunk_size:?}, {}: {:?}",
+                        String::from_utf8_lossy(str),
+                        tree_layout.get_label(),
+                        report.mismatches
+                    );
+                    total_report.merge(&report);
+                }
+            }
+        });
+        assert_reachable_branches_checked(&total_report);
+    }
+
+    #[test]
+    fn rules_agree_with_oracle_on_all_candidate_pairs() {
+        // Every pair of LSs that "rules" could be asked about: the Child LS has size "s" and the
+        // Parent LS is shorter, with the same first "s" chars (so it goes on in the next Factor).
+        let mut monitor = Monitor::new();
+        monitor.enable_rules_oracle();
+        for_each_test_string(200, |str| {
+            for chunk_size in [None, Some(1), Some(2), Some(3), Some(5)] {
+                let factorization = FactorizationStrategy::Icfl
+                    .get_factorizer(Chunking::new(chunk_size))
+                    .factorize(str);
+                let factor_indexes = &factorization.factor_indexes;
+                let mut ls_sizes = Vec::with_capacity(str.len());
+                for i in 0..factor_indexes.len() {
+                    let factor_end = get_factor_end(factor_indexes, i, str.len());
+                    ls_sizes.extend(
+                        (factor_indexes[i]..factor_end).map(|ls_index| factor_end - ls_index),
+                    );
+                }
+                for child_ls_index in 0..str.len() {
+                    let child_ls_size = ls_sizes[child_ls_index];
+                    let child_ls = &str[child_ls_index..child_ls_index + child_ls_size];
+                    for parent_ls_index in 0..str.len() - child_ls_size {
+                        if ls_sizes[parent_ls_index] < child_ls_size
+                            && str[parent_ls_index..].starts_with(child_ls)
+                        {
+                            rules_safe(
+                                parent_ls_index,
+                                child_ls_index,
+                                child_ls_size,
+                                str,
+                                &factorization.icfl_indexes,
+                                &factorization.idx_to_is_custom,
+                                &factorization.idx_to_icfl_factor,
+                                &mut monitor,
+                                true,
+                            );
+                        }
+                    }
+                }
+            }
+        });
+        let report = monitor.rules_oracle.unwrap();
+        assert!(report.mismatches.is_empty(), "{:?}", report.mismatches);
+        assert_reachable_branches_checked(&report);
+    }
+
+    #[test]
+    fn rules_oracle_reports_of_threads_are_merged() {
+        for_each_test_string(45, |str| {
+            for chunk_size in [None, Some(2), Some(4)] {
+                for tree_layout in [TreeLayout::Nested, TreeLayout::Arena] {
+                    let expected = get_rules_oracle_report(str, chunk_size, tree_layout, 1);
+                    for num_threads in [2, 3] {
+                        let given =
+                            get_rules_oracle_report(str, chunk_size, tree_layout, num_threads);
+                        assert_eq!(given.num_checks, expected.num_checks);
+                        assert_eq!(given.num_checks_by_branch, expected.num_checks_by_branch);
+                        assert_eq!(given.mismatches.len(), expected.mismatches.len());
+                    }
+                }
+            }
+        });
     }
 }
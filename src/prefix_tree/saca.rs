@@ -1,43 +1,97 @@
 use crate::prefix_tree::monitor::Monitor;
+use crate::prefix_tree::parallel::run_jobs_in_parallel_with_state;
+use crate::prefix_tree::print::get_string_clone;
 use crate::prefix_tree::rules::rules_safe;
+use crate::prefix_tree::symbol::Symbol;
 use crate::prefix_tree::tree::{Tree, TreeNode};
 
-impl<'a> Tree<'a> {
+impl<'a, T: Symbol> Tree<'a, T> {
     pub fn compute_suffix_array(
         &self,
-        str: &str,
-        icfl_indexes: &Vec<usize>,
-        idx_to_is_custom: &Vec<bool>,
-        idx_to_icfl_factor: &Vec<usize>,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
         monitor: &mut Monitor,
+        mut lcp_bounds: Option<&mut Vec<usize>>,
     ) -> Vec<usize> {
+        // LCP Bounds (optional): for each SA item, how many chars it surely shares with the
+        // previous SA item, known for free from the Prefix Tree (see "suffix_array::lcp").
         let mut suffix_array = Vec::with_capacity(str.len());
+        let mut rankings_buffers = RankingsBuffers::new();
         for (_, child_node) in &self.root.children {
             // Visiting from all First Layer Nodes to all Leafs (avoiding Root Node).
             self.get_common_prefix_partition(
                 child_node,
                 &child_node.rankings,
+                0, // Different First Layer Nodes share nothing.
                 str,
                 icfl_indexes,
                 idx_to_is_custom,
                 idx_to_icfl_factor,
                 monitor,
+                &mut rankings_buffers,
                 &mut suffix_array,
+                lcp_bounds.as_deref_mut(),
             );
         }
         suffix_array
     }
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute_suffix_array_parallel(
+        &self,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        lcp_bounds: Option<&mut Vec<usize>>,
+        num_threads: usize,
+    ) -> Vec<usize> {
+        let first_layer = &self.root.children;
+        compute_suffix_array_by_first_layer_blocks(
+            first_layer.len(),
+            str.len(),
+            monitor,
+            lcp_bounds,
+            num_threads,
+            |i_child, monitor, rankings_buffers, block, block_lcp_bounds| {
+                let (_, child_node) = &first_layer[i_child];
+                self.get_common_prefix_partition(
+                    child_node,
+                    &child_node.rankings,
+                    0, // Different First Layer Nodes share nothing.
+                    str,
+                    icfl_indexes,
+                    idx_to_is_custom,
+                    idx_to_icfl_factor,
+                    monitor,
+                    rankings_buffers,
+                    block,
+                    block_lcp_bounds,
+                );
+            },
+        )
+    }
+    #[allow(clippy::too_many_arguments)]
     fn get_common_prefix_partition(
         &self,
-        self_node: &TreeNode<'a>,
-        self_rks: &Vec<usize>,
-        str: &str,
-        icfl_indexes: &Vec<usize>,
-        idx_to_is_custom: &Vec<bool>,
-        idx_to_icfl_factor: &Vec<usize>,
+        self_node: &TreeNode<'a, T>,
+        self_rks: &[usize],
+        shared_len_before: usize,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
         monitor: &mut Monitor,
+        rankings_buffers: &mut RankingsBuffers,
         suffix_array: &mut Vec<usize>,
+        mut lcp_bounds: Option<&mut Vec<usize>>,
     ) {
+        // All Self Rankings (and so all SA items inserted from here) share the Self LS, so they
+        // share "self_node.suffix_len" chars with each other. The first one inserted only shares
+        // "shared_len_before" chars with the SA item before it (if any).
+        let suffix_array_len_before = suffix_array.len();
         let mut position = 0;
 
         // + Extra
@@ -56,7 +110,7 @@ impl<'a> Tree<'a> {
                 win_min,
                 win_max,
                 child_new_rankings,
-            ) = self.calculate_windows_and_child_shared_rankings(
+            ) = calculate_windows_and_child_shared_rankings(
                 child_node.suffix_len,
                 &child_node.rankings,
                 self_rks, // As Parent's Rankings.
@@ -66,6 +120,7 @@ impl<'a> Tree<'a> {
                 idx_to_is_custom,
                 idx_to_icfl_factor,
                 monitor,
+                rankings_buffers,
             );
 
             // SELF CPP: Self Rankings from left to Child WIN-MIN.
@@ -82,33 +137,62 @@ impl<'a> Tree<'a> {
                 }
                 // - Extra
 
+                if let Some(lcp_bounds) = lcp_bounds.as_deref_mut() {
+                    push_lcp_bounds(
+                        lcp_bounds,
+                        portion_to_insert.len(),
+                        if suffix_array.len() > suffix_array_len_before {
+                            self_node.suffix_len
+                        } else {
+                            shared_len_before
+                        },
+                        self_node.suffix_len,
+                    );
+                }
                 suffix_array.extend(portion_to_insert);
                 // position = win_min; // Here useless but meaningful.
             }
             position = win_max;
 
             // SELF CPP: Child Rankings
+            let child_shared_len_before = if suffix_array.len() > suffix_array_len_before {
+                self_node.suffix_len
+            } else {
+                shared_len_before
+            };
             if let Some(child_new_rankings) = child_new_rankings {
                 self.get_common_prefix_partition(
-                    &child_node,
+                    child_node,
                     &child_new_rankings,
+                    child_shared_len_before,
                     str,
                     icfl_indexes,
                     idx_to_is_custom,
                     idx_to_icfl_factor,
                     monitor,
+                    rankings_buffers,
                     suffix_array,
+                    lcp_bounds.as_deref_mut(),
                 );
+
+                // + Extra
+                monitor.drop_aux_rankings(child_new_rankings.len());
+                // - Extra
+
+                rankings_buffers.give_back(child_new_rankings);
             } else {
                 self.get_common_prefix_partition(
-                    &child_node,
+                    child_node,
                     &child_node.rankings,
+                    child_shared_len_before,
                     str,
                     icfl_indexes,
                     idx_to_is_custom,
                     idx_to_icfl_factor,
                     monitor,
+                    rankings_buffers,
                     suffix_array,
+                    lcp_bounds.as_deref_mut(),
                 );
             };
         }
@@ -127,127 +211,117 @@ impl<'a> Tree<'a> {
             }
             // - Extra
 
+            if let Some(lcp_bounds) = lcp_bounds {
+                push_lcp_bounds(
+                    lcp_bounds,
+                    portion_to_insert.len(),
+                    if suffix_array.len() > suffix_array_len_before {
+                        self_node.suffix_len
+                    } else {
+                        shared_len_before
+                    },
+                    self_node.suffix_len,
+                );
+            }
             suffix_array.extend(portion_to_insert);
             // position = self_rks.len(); // Here useless but meaningful.
         }
     }
-    fn calculate_windows_and_child_shared_rankings(
-        &self,
-        self_ls_size: usize,
-        self_rks: &Vec<usize>,
-        parent_rks: &Vec<usize>,
-        parent_rks_i_from: usize,
-        str: &str,
-        icfl_indexes: &Vec<usize>,
-        idx_to_is_custom: &Vec<bool>,
-        idx_to_icfl_factor: &Vec<usize>,
-        monitor: &mut Monitor,
-    ) -> (
-        usize,              // Win. Min (incl.)
-        usize,              // Win. Max (excl.)
-        Option<Vec<usize>>, // New Self Node's Rankings
-    ) {
-        let self_ls = &str[self_rks[0]..self_rks[0] + self_ls_size];
-
-        // Note: Binary Search tried before, not much of an improvement :_(
-
-        let mut i_parent = parent_rks_i_from;
-        while i_parent < parent_rks.len() {
-            let curr_parent_ls_index = parent_rks[i_parent];
-            let curr_parent_ls = &str
-                [curr_parent_ls_index..usize::min(curr_parent_ls_index + self_ls_size, str.len())];
-            // Safety is required here: "usize::min".
-
-            // + Extra
-            // TODO: Monitor string compare
-            monitor.execution_outcome.monitor_new_local_suffix_compare();
-            // - Extra
-
-            if curr_parent_ls >= self_ls {
-                // Found a Parent LS that is >= Self LS.
-                break;
-            }
-            // Until now, Parent LSs are < Self LS.
-            i_parent += 1;
-        }
-        let win_min = i_parent;
-        if win_min >= parent_rks.len() {
-            // All Parent LSs are < Self LS.
-            let win_max = i_parent;
-
-            // + Extra
-            if cfg!(feature = "verbose") {
-                let parent_left = &parent_rks[parent_rks_i_from..win_min];
-                let parent_window = &parent_rks[win_min..win_max];
-                let parent_right = &parent_rks[win_max..];
-                println!(
-                    "{}# In-prefix merge: Parent Rankings={:?}, Self Rankings={:?} -> {:?} smaller, {:?} equal, {:?} greater",
-                    " ".repeat(self_ls_size), &parent_rks[parent_rks_i_from..],
-                    self_rks, parent_left, parent_window, parent_right,
-                );
-            }
-            // - Extra
+}
 
-            return (win_min, win_max, None);
+// Each First Layer Node produces a contiguous block of the Suffix Array, independent from the
+// others. Threads compute blocks one at a time, then blocks are concatenated in the order of the
+// First Layer (with their LCP Bounds, if requested). Rankings Buffers are reused by all the
+// blocks of a thread.
+pub(crate) fn compute_suffix_array_by_first_layer_blocks<
+    F: Fn(usize, &mut Monitor, &mut RankingsBuffers, &mut Vec<usize>, Option<&mut Vec<usize>>) + Sync,
+>(
+    first_layer_len: usize,
+    str_len: usize,
+    monitor: &mut Monitor,
+    mut lcp_bounds: Option<&mut Vec<usize>>,
+    num_threads: usize,
+    compute_block: F, // Called with the index of the First Layer Node.
+) -> Vec<usize> {
+    let with_lcp_bounds = lcp_bounds.is_some();
+    let blocks = run_jobs_in_parallel_with_state(
+        first_layer_len,
+        num_threads,
+        monitor,
+        RankingsBuffers::new,
+        |i_child, monitor, rankings_buffers| {
+            let mut block = Vec::new();
+            let mut block_lcp_bounds = if with_lcp_bounds {
+                Some(Vec::new())
+            } else {
+                None
+            };
+            compute_block(
+                i_child,
+                monitor,
+                rankings_buffers,
+                &mut block,
+                block_lcp_bounds.as_mut(),
+            );
+            (block, block_lcp_bounds)
+        },
+    );
+    let mut suffix_array = Vec::with_capacity(str_len);
+    for (block, block_lcp_bounds) in blocks {
+        suffix_array.extend(block);
+        if let (Some(lcp_bounds), Some(block_lcp_bounds)) =
+            (lcp_bounds.as_deref_mut(), block_lcp_bounds)
+        {
+            lcp_bounds.extend(block_lcp_bounds);
         }
+    }
+    suffix_array
+}
 
-        // Curr. Parent LS is the first >= Self LS.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn calculate_windows_and_child_shared_rankings<T: Symbol>(
+    self_ls_size: usize,
+    self_rks: &[usize],
+    parent_rks: &[usize],
+    parent_rks_i_from: usize,
+    str: &[T],
+    icfl_indexes: &[usize],
+    idx_to_is_custom: &[bool],
+    idx_to_icfl_factor: &[usize],
+    monitor: &mut Monitor,
+    rankings_buffers: &mut RankingsBuffers,
+) -> (
+    usize,              // Win. Min (incl.)
+    usize,              // Win. Max (excl.)
+    Option<Vec<usize>>, // New Self Node's Rankings
+) {
+    let self_ls = &str[self_rks[0]..self_rks[0] + self_ls_size];
+
+    // Note: Binary Search tried before, not much of an improvement :_(
+
+    let mut i_parent = parent_rks_i_from;
+    while i_parent < parent_rks.len() {
         let curr_parent_ls_index = parent_rks[i_parent];
         let curr_parent_ls =
             &str[curr_parent_ls_index..usize::min(curr_parent_ls_index + self_ls_size, str.len())];
-        // Safety is optional here: "usize::min".
+        // Safety is required here: "usize::min".
 
         // + Extra
         // TODO: Monitor string compare
         monitor.execution_outcome.monitor_new_local_suffix_compare();
         // - Extra
 
-        if curr_parent_ls > self_ls {
-            // Curr. Parent LS is the first > Self LS.
-            // There is no Parent LS = Self LS, so min=max.
-            let win_max = win_min;
-
-            // + Extra
-            if cfg!(feature = "verbose") {
-                let parent_left = &parent_rks[parent_rks_i_from..win_min];
-                let parent_window = &parent_rks[win_min..win_max];
-                let parent_right = &parent_rks[win_max..];
-                println!(
-                    "{}# In-prefix merge: Parent Rankings={:?}, Self Rankings={:?} -> {:?} smaller, {:?} equal, {:?} greater",
-                    " ".repeat(self_ls_size), &parent_rks[parent_rks_i_from..],
-                    self_rks, parent_left, parent_window, parent_right,
-                );
-            }
-            // - Extra
-
-            return (win_min, win_max, None);
+        if curr_parent_ls >= self_ls {
+            // Found a Parent LS that is >= Self LS.
+            break;
         }
-
-        // Curr. Parent LS is the first = Self LS.
+        // Until now, Parent LSs are < Self LS.
         i_parent += 1;
-        while i_parent < parent_rks.len() {
-            let curr_parent_ls_index = parent_rks[i_parent];
-            let curr_parent_ls = &str
-                [curr_parent_ls_index..usize::min(curr_parent_ls_index + self_ls_size, str.len())];
-            // Safety is optional here: "usize::min".
-
-            // + Extra
-            // TODO: Monitor string compare
-            monitor.execution_outcome.monitor_new_local_suffix_compare();
-            // - Extra
-
-            if curr_parent_ls > self_ls {
-                // Found a Parent LS that is > Self LS.
-                break;
-            }
-            // Until now, Parent LSs are <= Self LS (before < now =).
-            i_parent += 1;
-        }
+    }
+    let win_min = i_parent;
+    if win_min >= parent_rks.len() {
+        // All Parent LSs are < Self LS.
         let win_max = i_parent;
-        i_parent = win_min;
-        // The Window for Comparing Rankings using "RULES":
-        // * starts from "i_parent" (included);
-        // * ends with "win_max" (excluded).
 
         // + Extra
         if cfg!(feature = "verbose") {
@@ -262,108 +336,228 @@ impl<'a> Tree<'a> {
         }
         // - Extra
 
-        // TODO: Avoid using auxiliary memory for Rankings
-        let mut new_self_rks = Vec::new();
-        let mut j_self = 0;
-        while i_parent < win_max && j_self < self_rks.len() {
-            let curr_parent_ls_index = parent_rks[i_parent];
-            let curr_self_ls_index = self_rks[j_self];
-            let result_rules = rules_safe(
-                curr_parent_ls_index,
-                curr_self_ls_index,
-                self_ls_size,
-                str,
-                icfl_indexes,
-                idx_to_is_custom,
-                idx_to_icfl_factor,
-                monitor,
-                false,
-            );
-            if !result_rules {
-                // + Extra
-                if cfg!(feature = "verbose") {
-                    let curr_parent_ls =
-                        &str[curr_parent_ls_index..curr_parent_ls_index + self_ls_size];
-                    // Safety is optional here: "usize::min".
-                    let curr_self_ls = &str[curr_self_ls_index..curr_self_ls_index + self_ls_size];
-                    println!(
-                        "{}/ compare parent=\"{}\" [{}] <-> child=\"{}\" [{}], child.suff.len={}: parent wins",
-                        " ".repeat(self_ls_size), curr_parent_ls, curr_parent_ls_index,
-                        curr_self_ls, curr_self_ls_index, self_ls_size,
-                    );
-                }
-                // - Extra
+        return (win_min, win_max, None);
+    }
 
-                new_self_rks.push(curr_parent_ls_index);
-                i_parent += 1;
-            } else {
-                // + Extra
-                if cfg!(feature = "verbose") {
-                    let curr_parent_ls =
-                        &str[curr_parent_ls_index..curr_parent_ls_index + self_ls_size];
-                    // Safety is optional here: "usize::min".
-                    let curr_self_ls = &str[curr_self_ls_index..curr_self_ls_index + self_ls_size];
-                    println!(
-                        "{}/ compare parent=\"{}\" [{}] <-> child=\"{}\" [{}], child.suff.len={}: child wins",
-                        " ".repeat(self_ls_size), curr_parent_ls, curr_parent_ls_index,
-                        curr_self_ls, curr_self_ls_index, self_ls_size,
-                    );
-                }
-                // - Extra
+    // Curr. Parent LS is the first >= Self LS.
+    let curr_parent_ls_index = parent_rks[i_parent];
+    let curr_parent_ls =
+        &str[curr_parent_ls_index..usize::min(curr_parent_ls_index + self_ls_size, str.len())];
+    // Safety is optional here: "usize::min".
 
-                new_self_rks.push(curr_self_ls_index);
-                j_self += 1;
-            }
-        }
+    // + Extra
+    // TODO: Monitor string compare
+    monitor.execution_outcome.monitor_new_local_suffix_compare();
+    // - Extra
+
+    if curr_parent_ls > self_ls {
+        // Curr. Parent LS is the first > Self LS.
+        // There is no Parent LS = Self LS, so min=max.
+        let win_max = win_min;
 
         // + Extra
         if cfg!(feature = "verbose") {
-            if j_self >= self_rks.len() {
-                println!(
-                    "{}/ no child rankings left to add",
-                    " ".repeat(self_ls_size),
-                );
-            }
+            let parent_left = &parent_rks[parent_rks_i_from..win_min];
+            let parent_window = &parent_rks[win_min..win_max];
+            let parent_right = &parent_rks[win_max..];
+            println!(
+                "{}# In-prefix merge: Parent Rankings={:?}, Self Rankings={:?} -> {:?} smaller, {:?} equal, {:?} greater",
+                " ".repeat(self_ls_size), &parent_rks[parent_rks_i_from..],
+                self_rks, parent_left, parent_window, parent_right,
+            );
         }
         // - Extra
 
-        while j_self < self_rks.len() {
-            let curr_self_ls_index = self_rks[j_self];
+        return (win_min, win_max, None);
+    }
+
+    // Curr. Parent LS is the first = Self LS.
+    i_parent += 1;
+    while i_parent < parent_rks.len() {
+        let curr_parent_ls_index = parent_rks[i_parent];
+        let curr_parent_ls =
+            &str[curr_parent_ls_index..usize::min(curr_parent_ls_index + self_ls_size, str.len())];
+        // Safety is optional here: "usize::min".
+
+        // + Extra
+        // TODO: Monitor string compare
+        monitor.execution_outcome.monitor_new_local_suffix_compare();
+        // - Extra
+
+        if curr_parent_ls > self_ls {
+            // Found a Parent LS that is > Self LS.
+            break;
+        }
+        // Until now, Parent LSs are <= Self LS (before < now =).
+        i_parent += 1;
+    }
+    let win_max = i_parent;
+    i_parent = win_min;
+    // The Window for Comparing Rankings using "RULES":
+    // * starts from "i_parent" (included);
+    // * ends with "win_max" (excluded).
+
+    // + Extra
+    if cfg!(feature = "verbose") {
+        let parent_left = &parent_rks[parent_rks_i_from..win_min];
+        let parent_window = &parent_rks[win_min..win_max];
+        let parent_right = &parent_rks[win_max..];
+        println!(
+            "{}# In-prefix merge: Parent Rankings={:?}, Self Rankings={:?} -> {:?} smaller, {:?} equal, {:?} greater",
+            " ".repeat(self_ls_size), &parent_rks[parent_rks_i_from..],
+            self_rks, parent_left, parent_window, parent_right,
+        );
+    }
+    // - Extra
 
+    // No allocation here, unless no buffer is free or it is too small (see "RankingsBuffers").
+    monitor.new_rankings_merge();
+    let mut new_self_rks = rankings_buffers.take(win_max - win_min + self_rks.len(), monitor);
+    let mut j_self = 0;
+    while i_parent < win_max && j_self < self_rks.len() {
+        let curr_parent_ls_index = parent_rks[i_parent];
+        let curr_self_ls_index = self_rks[j_self];
+        let result_rules = rules_safe(
+            curr_parent_ls_index,
+            curr_self_ls_index,
+            self_ls_size,
+            str,
+            icfl_indexes,
+            idx_to_is_custom,
+            idx_to_icfl_factor,
+            monitor,
+            monitor.is_rules_oracle_enabled(),
+        );
+        if !result_rules {
             // + Extra
             if cfg!(feature = "verbose") {
+                let curr_parent_ls = get_string_clone(
+                    &str[curr_parent_ls_index..curr_parent_ls_index + self_ls_size],
+                );
+                // Safety is optional here: "usize::min".
+                let curr_self_ls =
+                    get_string_clone(&str[curr_self_ls_index..curr_self_ls_index + self_ls_size]);
                 println!(
-                    "{}/ adding   child=\"{}\" [{}], child.suff.len={}",
-                    " ".repeat(self_ls_size),
-                    &str[curr_self_ls_index..curr_self_ls_index + self_ls_size],
-                    curr_self_ls_index,
-                    self_ls_size,
+                    "{}/ compare parent=\"{}\" [{}] <-> child=\"{}\" [{}], child.suff.len={}: parent wins",
+                    " ".repeat(self_ls_size), curr_parent_ls, curr_parent_ls_index,
+                    curr_self_ls, curr_self_ls_index, self_ls_size,
                 );
             }
             // - Extra
 
-            new_self_rks.push(curr_self_ls_index);
-            j_self += 1;
-        }
-        while i_parent < win_max {
-            let curr_parent_ls_index = parent_rks[i_parent];
-
+            new_self_rks.push(curr_parent_ls_index);
+            i_parent += 1;
+        } else {
             // + Extra
             if cfg!(feature = "verbose") {
-                println!(
-                    "{}/ adding  parent=\"{}\" [{}], parent.suff.len={}",
-                    " ".repeat(self_ls_size),
+                let curr_parent_ls = get_string_clone(
                     &str[curr_parent_ls_index..curr_parent_ls_index + self_ls_size],
-                    curr_parent_ls_index,
-                    self_ls_size,
+                );
+                // Safety is optional here: "usize::min".
+                let curr_self_ls =
+                    get_string_clone(&str[curr_self_ls_index..curr_self_ls_index + self_ls_size]);
+                println!(
+                    "{}/ compare parent=\"{}\" [{}] <-> child=\"{}\" [{}], child.suff.len={}: child wins",
+                    " ".repeat(self_ls_size), curr_parent_ls, curr_parent_ls_index,
+                    curr_self_ls, curr_self_ls_index, self_ls_size,
                 );
             }
             // - Extra
 
-            new_self_rks.push(curr_parent_ls_index);
-            i_parent += 1;
+            new_self_rks.push(curr_self_ls_index);
+            j_self += 1;
+        }
+    }
+
+    // + Extra
+    if cfg!(feature = "verbose") && j_self >= self_rks.len() {
+        println!(
+            "{}/ no child rankings left to add",
+            " ".repeat(self_ls_size),
+        );
+    }
+    // - Extra
+
+    while j_self < self_rks.len() {
+        let curr_self_ls_index = self_rks[j_self];
+
+        // + Extra
+        if cfg!(feature = "verbose") {
+            println!(
+                "{}/ adding   child=\"{}\" [{}], child.suff.len={}",
+                " ".repeat(self_ls_size),
+                get_string_clone(&str[curr_self_ls_index..curr_self_ls_index + self_ls_size]),
+                curr_self_ls_index,
+                self_ls_size,
+            );
+        }
+        // - Extra
+
+        new_self_rks.push(curr_self_ls_index);
+        j_self += 1;
+    }
+    while i_parent < win_max {
+        let curr_parent_ls_index = parent_rks[i_parent];
+
+        // + Extra
+        if cfg!(feature = "verbose") {
+            println!(
+                "{}/ adding  parent=\"{}\" [{}], parent.suff.len={}",
+                " ".repeat(self_ls_size),
+                get_string_clone(&str[curr_parent_ls_index..curr_parent_ls_index + self_ls_size]),
+                curr_parent_ls_index,
+                self_ls_size,
+            );
+        }
+        // - Extra
+
+        new_self_rks.push(curr_parent_ls_index);
+        i_parent += 1;
+    }
+
+    // + Extra
+    monitor.new_aux_rankings(new_self_rks.len());
+    // - Extra
+
+    (win_min, win_max, Some(new_self_rks))
+}
+
+// Buffers for the Rankings merged in Phase 3, reused from Node to Node: a buffer is taken for a
+// Child Node and given back when its visit ends, so only one buffer per Tree depth is allocated
+// (and grown when needed).
+pub(crate) struct RankingsBuffers {
+    free_buffers: Vec<Vec<usize>>,
+}
+impl RankingsBuffers {
+    pub fn new() -> Self {
+        Self {
+            free_buffers: Vec::new(),
         }
+    }
+    pub fn take(&mut self, capacity: usize, monitor: &mut Monitor) -> Vec<usize> {
+        let mut buffer = self.free_buffers.pop().unwrap_or_default();
+        buffer.clear();
+        if buffer.capacity() < capacity {
+            buffer.reserve(capacity);
+            monitor.new_rankings_allocation();
+        }
+        buffer
+    }
+    pub fn give_back(&mut self, buffer: Vec<usize>) {
+        self.free_buffers.push(buffer);
+    }
+}
 
-        (win_min, win_max, Some(new_self_rks))
+pub(crate) fn push_lcp_bounds(
+    lcp_bounds: &mut Vec<usize>,
+    portion_len: usize,
+    first_shared_len: usize,
+    others_shared_len: usize,
+) {
+    if portion_len > 0 {
+        lcp_bounds.push(first_shared_len);
+        for _ in 1..portion_len {
+            lcp_bounds.push(others_shared_len);
+        }
     }
 }
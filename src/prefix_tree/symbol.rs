@@ -0,0 +1,24 @@
+use std::fmt::Debug;
+
+// Alphabet of the text: any totally ordered and copyable symbol. Bytes and chars print as they
+// are, every other alphabet (for example "u16"/"u32" token ids) falls back to its Debug format.
+// Symbols are shared between threads in the parallel phases, so they must be "Send + Sync".
+pub trait Symbol: Ord + Copy + Debug + Send + Sync {
+    fn push_label(&self, label: &mut String) {
+        label.push_str(&format!("{:?} ", self));
+    }
+}
+impl Symbol for u8 {
+    fn push_label(&self, label: &mut String) {
+        label.push(*self as char);
+    }
+}
+impl Symbol for char {
+    fn push_label(&self, label: &mut String) {
+        label.push(*self);
+    }
+}
+impl Symbol for u16 {}
+impl Symbol for u32 {}
+impl Symbol for u64 {}
+impl Symbol for usize {}
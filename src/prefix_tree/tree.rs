@@ -1,19 +1,111 @@
 use crate::factorization::get_max_factor_size;
+use crate::prefix_tree::layout::PrefixTree;
 use crate::prefix_tree::monitor::Monitor;
+use crate::prefix_tree::parallel::run_jobs_in_parallel;
 use crate::prefix_tree::print::get_string_clone;
+use crate::prefix_tree::symbol::Symbol;
+use std::collections::BTreeMap;
 
-pub fn create_tree<'a>(
-    str: &'a [char],
-    factor_indexes: &Vec<usize>,
-    icfl_indexes: &Vec<usize>,
-    idx_to_is_custom: &Vec<bool>,
+pub fn create_tree<'a, T: Symbol>(
+    str: &'a [T],
+    factor_indexes: &[usize],
+    icfl_indexes: &[usize],
+    idx_to_is_custom: &[bool],
     monitor: &mut Monitor,
-) -> Tree<'a> {
-    let str_length = str.len();
-    let max_factor_size = get_max_factor_size(&factor_indexes, str_length);
-    let last_icfl_factor_size = str_length - icfl_indexes[icfl_indexes.len() - 1];
+) -> Tree<'a, T> {
+    let mut tree = Tree::new();
+    for_each_local_suffix(
+        str.len(),
+        factor_indexes,
+        icfl_indexes,
+        idx_to_is_custom,
+        |ls_index, ls_size, is_custom_ls| {
+            tree.add(ls_index, ls_size, is_custom_ls, str, monitor);
+
+            // + Extra
+            if cfg!(feature = "verbose") {
+                tree.print();
+            }
+            // - Extra
+        },
+    );
+    tree
+}
 
+pub fn create_tree_parallel<'a, T: Symbol>(
+    str: &'a [T],
+    factor_indexes: &[usize],
+    icfl_indexes: &[usize],
+    idx_to_is_custom: &[bool],
+    monitor: &mut Monitor,
+    num_threads: usize,
+) -> Tree<'a, T> {
+    // The First Layer Nodes of each bucket (see "get_local_suffixes_buckets") are built on their
+    // own thread. The resulting Tree is identical to the one of "create_tree".
+    let buckets =
+        get_local_suffixes_buckets(str, factor_indexes, icfl_indexes, idx_to_is_custom, monitor);
+    let first_layer_nodes_by_bucket =
+        run_jobs_in_parallel(buckets.len(), num_threads, monitor, |i_bucket, monitor| {
+            let mut subtree = Tree::new();
+            for &(ls_index, ls_size, is_custom_ls) in &buckets[i_bucket] {
+                subtree.add(ls_index, ls_size, is_custom_ls, str, monitor);
+            }
+            subtree.root.children
+        });
     let mut tree = Tree::new();
+    for first_layer_nodes in first_layer_nodes_by_bucket {
+        tree.root.children.extend(first_layer_nodes);
+    }
+
+    // + Extra
+    if cfg!(feature = "verbose") {
+        tree.print();
+    }
+    // - Extra
+
+    tree
+}
+
+pub(crate) fn get_local_suffixes_buckets<T: Symbol>(
+    str: &[T],
+    factor_indexes: &[usize],
+    icfl_indexes: &[usize],
+    idx_to_is_custom: &[bool],
+    monitor: &mut Monitor,
+) -> Vec<Vec<(usize, usize, bool)>> {
+    // LSs with different first symbols never end up in the same First Layer Node, so LSs are
+    // bucketed by first symbol (keeping the order of "create_tree" inside each bucket) to build
+    // the First Layer Nodes of each bucket independently.
+    monitor.p2_tree_bucketing.start();
+    let mut buckets = BTreeMap::new();
+    for_each_local_suffix(
+        str.len(),
+        factor_indexes,
+        icfl_indexes,
+        idx_to_is_custom,
+        |ls_index, ls_size, is_custom_ls| {
+            buckets.entry(str[ls_index]).or_insert_with(Vec::new).push((
+                ls_index,
+                ls_size,
+                is_custom_ls,
+            ));
+        },
+    );
+    // Sorted by first symbol, like First Layer Nodes.
+    let buckets = buckets.into_values().collect::<Vec<_>>();
+    monitor.p2_tree_bucketing.stop();
+    buckets
+}
+
+pub(crate) fn for_each_local_suffix<F: FnMut(usize, usize, bool)>(
+    str_length: usize,
+    factor_indexes: &[usize],
+    icfl_indexes: &[usize],
+    idx_to_is_custom: &[bool],
+    mut on_local_suffix: F, // Called with "ls_index", "ls_size" and "is_custom_ls".
+) {
+    let max_factor_size = get_max_factor_size(factor_indexes, str_length);
+    let last_icfl_factor_size = str_length - icfl_indexes[icfl_indexes.len() - 1];
 
     for ls_size in 1..=max_factor_size {
         // Looking for LSs with length "ls_size":
@@ -23,13 +115,9 @@ pub fn create_tree<'a>(
         // LSs from Canonical Factors (last ICFL Factor)
         if ls_size <= last_icfl_factor_size {
             let ls_index = str_length - ls_size;
-            tree.add(ls_index, ls_size, false, str, monitor);
-
-            // + Extra
-            if cfg!(feature = "verbose") {
-                tree.print();
+            if !idx_to_is_custom[ls_index] {
+                on_local_suffix(ls_index, ls_size, false);
             }
-            // - Extra
         }
         // LSs from Canonical Factors (from first to second-last ICFL Factors)
         for i in 0..icfl_indexes.len() - 1 {
@@ -37,41 +125,37 @@ pub fn create_tree<'a>(
             let curr_icfl_factor_size = next_icfl_factor_idx - icfl_indexes[i];
             if ls_size <= curr_icfl_factor_size {
                 let ls_index = next_icfl_factor_idx - ls_size;
-                tree.add(ls_index, ls_size, false, str, monitor);
-
-                // + Extra
-                if cfg!(feature = "verbose") {
-                    tree.print();
+                if !idx_to_is_custom[ls_index] {
+                    on_local_suffix(ls_index, ls_size, false);
                 }
-                // - Extra
+                // Else: in a Custom Factor, considered below.
             }
         }
         // LSs from Custom Factors
-        for i in 0..factor_indexes.len() - 1 {
-            let curr_factor_size = factor_indexes[i + 1] - factor_indexes[i];
+        for i in 0..factor_indexes.len() {
+            let next_factor_idx = if i < factor_indexes.len() - 1 {
+                factor_indexes[i + 1]
+            } else {
+                str_length
+            };
+            let curr_factor_size = next_factor_idx - factor_indexes[i];
             if ls_size <= curr_factor_size {
-                let ls_index = factor_indexes[i + 1] - ls_size;
+                let ls_index = next_factor_idx - ls_size;
                 if idx_to_is_custom[ls_index] {
-                    tree.add(ls_index, ls_size, true, str, monitor);
-
-                    // + Extra
-                    if cfg!(feature = "verbose") {
-                        tree.print();
-                    }
-                    // - Extra
+                    on_local_suffix(ls_index, ls_size, true);
                 }
                 // Else: Canonical Factor, already considered.
             }
         }
     }
-
-    tree
 }
 
-pub struct Tree<'a> {
-    pub root: TreeNode<'a>,
+#[derive(PartialEq, Debug)]
+pub struct Tree<'a, T: Symbol> {
+    pub root: TreeNode<'a, T>,
 }
-impl<'a> Tree<'a> {
+impl<'a, T: Symbol> Tree<'a, T> {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
             root: TreeNode::new(0),
@@ -82,7 +166,7 @@ impl<'a> Tree<'a> {
         ls_index: usize,
         ls_size: usize,
         is_custom_ls: bool,
-        str: &'a [char],
+        str: &'a [T],
         monitor: &mut Monitor,
     ) {
         self.root
@@ -90,12 +174,83 @@ impl<'a> Tree<'a> {
     }
 }
 
-pub struct TreeNode<'a> {
+impl<'a, T: Symbol> PrefixTree<T> for Tree<'a, T> {
+    fn visit_nodes(&self, on_node: &mut dyn FnMut(usize, &[T], &[usize])) {
+        let mut stack = vec![(0, &[][..], &self.root)];
+        while let Some((level, prefix, node)) = stack.pop() {
+            on_node(level, prefix, &node.rankings);
+            for (child_prefix, child_node) in node.children.iter().rev() {
+                stack.push((level + 1, child_prefix, child_node));
+            }
+        }
+    }
+    fn get_nodes_and_rankings_count(&self) -> (usize, usize) {
+        let mut nodes_count = 0;
+        let mut rankings_count = 0;
+        let mut stack = self.root.children.iter().collect::<Vec<_>>();
+        while let Some((_, node)) = stack.pop() {
+            nodes_count += 1;
+            rankings_count += node.rankings.len();
+            stack.extend(node.children.iter());
+        }
+        (nodes_count, rankings_count)
+    }
+    fn get_nodes_bytes(&self) -> usize {
+        // Every Node but the Root one is stored next to its prefix in the Parent's Children.
+        let (nodes_count, _) = self.get_nodes_and_rankings_count();
+        nodes_count * std::mem::size_of::<(&[T], TreeNode<T>)>()
+    }
+    fn compute_suffix_array(
+        &self,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        lcp_bounds: Option<&mut Vec<usize>>,
+    ) -> Vec<usize> {
+        // Inherent, see "saca".
+        Tree::compute_suffix_array(
+            self,
+            str,
+            icfl_indexes,
+            idx_to_is_custom,
+            idx_to_icfl_factor,
+            monitor,
+            lcp_bounds,
+        )
+    }
+    fn compute_suffix_array_parallel(
+        &self,
+        str: &[T],
+        icfl_indexes: &[usize],
+        idx_to_is_custom: &[bool],
+        idx_to_icfl_factor: &[usize],
+        monitor: &mut Monitor,
+        lcp_bounds: Option<&mut Vec<usize>>,
+        num_threads: usize,
+    ) -> Vec<usize> {
+        // Inherent, see "saca".
+        Tree::compute_suffix_array_parallel(
+            self,
+            str,
+            icfl_indexes,
+            idx_to_is_custom,
+            idx_to_icfl_factor,
+            monitor,
+            lcp_bounds,
+            num_threads,
+        )
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct TreeNode<'a, T: Symbol> {
     pub suffix_len: usize,
     pub rankings: Vec<usize>,
-    pub children: Vec<(&'a [char], TreeNode<'a>)>,
+    pub children: Vec<(&'a [T], TreeNode<'a, T>)>,
 }
-impl<'a> TreeNode<'a> {
+impl<'a, T: Symbol> TreeNode<'a, T> {
     pub fn new(suffix_len: usize) -> Self {
         Self {
             suffix_len,
@@ -109,7 +264,7 @@ impl<'a> TreeNode<'a> {
         ls_size: usize,
         i_char: usize,
         is_custom_ls: bool,
-        str: &'a [char],
+        str: &'a [T],
         monitor: &mut Monitor,
     ) {
         if i_char == ls_size {
@@ -119,7 +274,7 @@ impl<'a> TreeNode<'a> {
             }
             // - Extra
 
-            self.update_rankings(ls_index, is_custom_ls, str, monitor);
+            update_rankings(&mut self.rankings, ls_index, is_custom_ls, str, monitor);
             return;
         }
 
@@ -181,7 +336,7 @@ impl<'a> TreeNode<'a> {
         }
         if p >= q {
             let mut new_node = TreeNode::new(ls_size);
-            new_node.update_rankings(ls_index, is_custom_ls, str, monitor);
+            update_rankings(&mut new_node.rankings, ls_index, is_custom_ls, str, monitor);
             self.children.insert(p, (rest_of_ls, new_node));
 
             // + Extra
@@ -202,30 +357,90 @@ impl<'a> TreeNode<'a> {
             // - Extra
         }
     }
-    fn update_rankings(
-        &mut self,
-        ls_index: usize,
-        is_custom_ls: bool,
-        str: &[char],
-        monitor: &mut Monitor,
-    ) {
-        if is_custom_ls {
-            let custom_gs = &str[ls_index..];
-            let idx = self.rankings.partition_point(|&gs_index| {
-                let gs = &str[gs_index..];
+}
 
-                // + Extra
-                // TODO: Monitor string compare
-                monitor
-                    .execution_outcome
-                    .monitor_new_global_suffix_compare();
-                // - Extra
+pub(crate) fn update_rankings<T: Symbol>(
+    rankings: &mut Vec<usize>,
+    ls_index: usize,
+    is_custom_ls: bool,
+    str: &[T],
+    monitor: &mut Monitor,
+) {
+    if is_custom_ls {
+        let custom_gs = &str[ls_index..];
+        let idx = rankings.partition_point(|&gs_index| {
+            let gs = &str[gs_index..];
+
+            // + Extra
+            // TODO: Monitor string compare
+            monitor
+                .execution_outcome
+                .monitor_new_global_suffix_compare();
+            // - Extra
+
+            gs <= custom_gs
+        });
+        rankings.insert(idx, ls_index);
+    } else {
+        rankings.push(ls_index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factorization::custom_factorization::Chunking;
+    use crate::factorization::factorizer::FactorizationStrategy;
+    use crate::test_utils::for_each_random_string;
 
-                gs <= custom_gs
-            });
-            self.rankings.insert(idx, ls_index);
-        } else {
-            self.rankings.push(ls_index);
+    fn assert_parallel_tree_is_identical(str: &[u8]) {
+        for chunk_size in [None, Some(1), Some(2), Some(3), Some(5)] {
+            let factorization = FactorizationStrategy::Icfl
+                .get_factorizer(Chunking::new(chunk_size))
+                .factorize(str);
+            let tree = create_tree(
+                str,
+                &factorization.factor_indexes,
+                &factorization.icfl_indexes,
+                &factorization.idx_to_is_custom,
+                &mut Monitor::new(),
+            );
+            for num_threads in [1, 2, 3, 8] {
+                let parallel_tree = create_tree_parallel(
+                    str,
+                    &factorization.factor_indexes,
+                    &factorization.icfl_indexes,
+                    &factorization.idx_to_is_custom,
+                    &mut Monitor::new(),
+                    num_threads,
+                );
+                assert_eq!(
+                    parallel_tree,
+                    tree,
+                    "str={:?}, chunk_size={chunk_size:?}, num_threads={num_threads}",
+                    String::from_utf8_lossy(str),
+                );
+            }
         }
     }
+
+    #[test]
+    fn parallel_tree_is_identical_on_known_strings() {
+        for str in [
+            &b"A"[..],
+            b"AAAAAAAA",
+            b"AAABCAABCADCAABCA",
+            b"GCAATAATGCGTATCAGCAATAATGCA",
+            b"ABRACADABRA",
+        ] {
+            assert_parallel_tree_is_identical(str);
+        }
+    }
+
+    #[test]
+    fn parallel_tree_is_identical_on_random_strings() {
+        for_each_random_string(16, 100, 1..=60, 1..=4, |str, _| {
+            assert_parallel_tree_is_identical(str);
+        });
+    }
 }
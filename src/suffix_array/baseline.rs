@@ -0,0 +1,161 @@
+use crate::suffix_array::classic_suffix_array::compute_classic_suffix_array;
+use crate::suffix_array::dc3::compute_suffix_array_dc3;
+use crate::suffix_array::prefix_doubling::compute_suffix_array_prefix_doubling;
+use crate::suffix_array::sais::compute_suffix_array_sais;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+// Suffix Array Construction Algorithms the Innovative one is compared with.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BaselineSaca {
+    Classic,
+    SaIs,
+    PrefixDoubling,
+    Dc3,
+}
+impl BaselineSaca {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "classic" => Ok(BaselineSaca::Classic),
+            "sa_is" => Ok(BaselineSaca::SaIs),
+            "prefix_doubling" => Ok(BaselineSaca::PrefixDoubling),
+            "dc3" => Ok(BaselineSaca::Dc3),
+            _ => Err(format!(
+                "Invalid baseline \"{}\", expected \"classic\", \"sa_is\", \"prefix_doubling\" or \"dc3\"",
+                value
+            )),
+        }
+    }
+    pub fn parse_list(expression: &str) -> Result<Vec<Self>, String> {
+        // Comma-separated, like "classic,sa_is", or "none".
+        if expression.trim() == "none" {
+            return Ok(Vec::new());
+        }
+        expression
+            .split(',')
+            .map(|value| BaselineSaca::parse(value.trim()))
+            .collect()
+    }
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            BaselineSaca::Classic => "classic",
+            BaselineSaca::SaIs => "sa_is",
+            BaselineSaca::PrefixDoubling => "prefix_doubling",
+            BaselineSaca::Dc3 => "dc3",
+        }
+    }
+}
+
+pub struct BaselineSuffixArrayComputationResults {
+    pub suffix_array: Vec<usize>,
+    pub duration: Duration,
+}
+pub fn compute_baseline_suffix_array(
+    baseline: BaselineSaca,
+    src: &str,
+) -> BaselineSuffixArrayComputationResults {
+    if baseline == BaselineSaca::Classic {
+        let classic_suffix_array_computation = compute_classic_suffix_array(src);
+        return BaselineSuffixArrayComputationResults {
+            suffix_array: classic_suffix_array_computation.suffix_array,
+            duration: classic_suffix_array_computation.duration,
+        };
+    }
+
+    let before = Instant::now();
+    let str = src.as_bytes();
+    let suffix_array = match baseline {
+        BaselineSaca::SaIs => compute_suffix_array_sais(str),
+        BaselineSaca::PrefixDoubling => compute_suffix_array_prefix_doubling(str),
+        BaselineSaca::Dc3 => compute_suffix_array_dc3(str),
+        BaselineSaca::Classic => unreachable!(),
+    };
+    let after = Instant::now();
+
+    BaselineSuffixArrayComputationResults {
+        suffix_array,
+        duration: after - before,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{for_each_random_string, get_repetitive_strings};
+
+    type Saca = fn(&[u8]) -> Vec<usize>;
+    const SACAS: [(&str, Saca); 3] = [
+        ("sa_is", compute_suffix_array_sais),
+        ("prefix_doubling", compute_suffix_array_prefix_doubling),
+        ("dc3", compute_suffix_array_dc3),
+    ];
+
+    fn get_naive_suffix_array(str: &[u8]) -> Vec<usize> {
+        let mut suffix_array = (0..str.len()).collect::<Vec<_>>();
+        suffix_array.sort_by(|&a, &b| str[a..].cmp(&str[b..]));
+        suffix_array
+    }
+    fn check(str: &[u8]) {
+        let expected = get_naive_suffix_array(str);
+        for (name, compute) in SACAS {
+            assert_eq!(
+                compute(str),
+                expected,
+                "{} on \"{}\"",
+                name,
+                String::from_utf8_lossy(str)
+            );
+        }
+    }
+
+    #[test]
+    fn sacas_on_random_strings() {
+        for_each_random_string(13, 500, 0..=100, 1..=5, |str, _| check(str));
+        // Also a larger alphabet, and lengths for the deeper recursions.
+        for_each_random_string(13, 20, 1000..=3000, 1..=26, |str, _| check(str));
+    }
+
+    #[test]
+    fn sacas_on_repetitive_strings() {
+        for str in get_repetitive_strings(80) {
+            check(&str);
+        }
+        check(&[b'A'; 5000]);
+        check(&b"AB".repeat(2500));
+        check(&b"AAB".repeat(1700));
+    }
+
+    #[test]
+    fn sacas_on_edge_cases() {
+        check(b"");
+        check(b"A");
+        check(&[0]);
+        check(&[255, 0, 255, 0, 0]);
+        check(&(0..=255).rev().collect::<Vec<u8>>());
+        check(b"mississippi");
+        check(b"banana");
+    }
+
+    #[test]
+    fn baselines_are_identical() {
+        for_each_random_string(13, 100, 0..=100, 1..=4, |str, _| {
+            let src = std::str::from_utf8(str).unwrap();
+            let expected = get_naive_suffix_array(str);
+            for baseline in [
+                BaselineSaca::Classic,
+                BaselineSaca::SaIs,
+                BaselineSaca::PrefixDoubling,
+                BaselineSaca::Dc3,
+            ] {
+                assert_eq!(
+                    compute_baseline_suffix_array(baseline, src).suffix_array,
+                    expected,
+                    "{} on \"{}\"",
+                    baseline.get_label(),
+                    src
+                );
+            }
+        });
+    }
+}
@@ -6,7 +6,7 @@ pub struct ClassicSuffixArrayComputationResults<'a> {
     pub suffix_array_pairs: Vec<(usize, &'a str)>,
     pub duration: Duration,
 }
-pub fn compute_classic_suffix_array(src: &str) -> ClassicSuffixArrayComputationResults {
+pub fn compute_classic_suffix_array(src: &str) -> ClassicSuffixArrayComputationResults<'_> {
     let before = Instant::now();
 
     let mut suffix_array_pairs = Vec::new();
@@ -38,3 +38,33 @@ pub fn compute_classic_suffix_array(src: &str) -> ClassicSuffixArrayComputationR
         duration,
     }
 }
+
+pub struct ClassicGeneralizedSuffixArrayComputationResults {
+    pub entries: Vec<(usize, usize)>,
+    pub duration: Duration,
+}
+pub fn compute_classic_generalized_suffix_array(
+    sequences: &[&str],
+) -> ClassicGeneralizedSuffixArrayComputationResults {
+    let before = Instant::now();
+
+    // Create array of global suffixes of every sequence
+    let mut entries = Vec::new();
+    for (sequence_id, &sequence) in sequences.iter().enumerate() {
+        for i in 0..sequence.len() {
+            entries.push((sequence_id, i));
+        }
+    }
+    // Create sort by comparing global suffixes, then sequence ids for equal ones
+    entries.sort_by(|&(a_sequence_id, a_i), &(b_sequence_id, b_i)| {
+        let a_string = &sequences[a_sequence_id][a_i..];
+        let b_string = &sequences[b_sequence_id][b_i..];
+        a_string
+            .cmp(b_string)
+            .then(a_sequence_id.cmp(&b_sequence_id))
+    });
+    let after = Instant::now();
+    let duration = after - before;
+
+    ClassicGeneralizedSuffixArrayComputationResults { entries, duration }
+}
@@ -0,0 +1,144 @@
+// DC3/Skew (Kärkkäinen, Sanders): sorting suffixes at positions "i mod 3 != 0" recursively, then
+// the others with them, then merging the two, linear time.
+pub fn compute_suffix_array_dc3(str: &[u8]) -> Vec<usize> {
+    let n = str.len();
+    if n <= 1 {
+        return (0..n).collect();
+    }
+    // Symbols shifted by one, so that "0" is used for padding.
+    let mut s = Vec::with_capacity(n + 3);
+    for &byte in str {
+        s.push(byte as usize + 1);
+    }
+    s.extend_from_slice(&[0, 0, 0]);
+    let mut suffix_array = vec![0; n];
+    dc3(&s, &mut suffix_array, n, 256);
+    suffix_array
+}
+
+fn dc3(s: &[usize], suffix_array: &mut [usize], n: usize, max_symbol: usize) {
+    // Requires "s[n] = s[n+1] = s[n+2] = 0" and symbols in "1..=max_symbol".
+    let n0 = n.div_ceil(3);
+    let n1 = (n + 1) / 3;
+    let n2 = n / 3;
+    let n02 = n0 + n2;
+
+    // Positions "i mod 3 != 0", with a dummy one when "n mod 3 == 1".
+    let mut s12 = vec![0; n02 + 3];
+    let mut sa12 = vec![0; n02 + 3];
+    let mut j = 0;
+    for i in 0..n + (n0 - n1) {
+        if i % 3 != 0 {
+            s12[j] = i;
+            j += 1;
+        }
+    }
+
+    // Radix sorting triples.
+    radix_pass(&s12, &mut sa12, &s[2..], n02, max_symbol);
+    radix_pass(&sa12, &mut s12, &s[1..], n02, max_symbol);
+    radix_pass(&s12, &mut sa12, s, n02, max_symbol);
+
+    // Naming triples.
+    let mut name = 0;
+    let mut prev_triple = None;
+    for &i in &sa12[..n02] {
+        let triple = (s[i], s[i + 1], s[i + 2]);
+        if prev_triple != Some(triple) {
+            name += 1;
+            prev_triple = Some(triple);
+        }
+        if i % 3 == 1 {
+            s12[i / 3] = name;
+        } else {
+            s12[i / 3 + n0] = name;
+        }
+    }
+
+    // Sorting positions "i mod 3 != 0", recursively if names are not unique.
+    if name < n02 {
+        dc3(&s12, &mut sa12, n02, name);
+        for k in 0..n02 {
+            s12[sa12[k]] = k + 1;
+        }
+    } else {
+        for k in 0..n02 {
+            sa12[s12[k] - 1] = k;
+        }
+    }
+
+    // Sorting positions "i mod 3 == 0" by first symbol and rank of the next suffix.
+    let mut s0 = vec![0; n0];
+    let mut sa0 = vec![0; n0];
+    let mut j = 0;
+    for &i in &sa12[..n02] {
+        if i < n0 {
+            s0[j] = 3 * i;
+            j += 1;
+        }
+    }
+    radix_pass(&s0, &mut sa0, s, n0, max_symbol);
+
+    // Merging.
+    let get_i = |t: usize| {
+        if sa12[t] < n0 {
+            sa12[t] * 3 + 1
+        } else {
+            (sa12[t] - n0) * 3 + 2
+        }
+    };
+    let mut p = 0;
+    let mut t = n0 - n1;
+    let mut k = 0;
+    while k < n {
+        let i = get_i(t);
+        let j = sa0[p];
+        let is_sa12_smaller = if sa12[t] < n0 {
+            (s[i], s12[sa12[t] + n0]) <= (s[j], s12[j / 3])
+        } else {
+            (s[i], s[i + 1], s12[sa12[t] - n0 + 1]) <= (s[j], s[j + 1], s12[j / 3 + n0])
+        };
+        if is_sa12_smaller {
+            suffix_array[k] = i;
+            t += 1;
+            if t == n02 {
+                // Only positions "i mod 3 == 0" left.
+                while p < n0 {
+                    k += 1;
+                    suffix_array[k] = sa0[p];
+                    p += 1;
+                }
+            }
+        } else {
+            suffix_array[k] = j;
+            p += 1;
+            if p == n0 {
+                // Only positions "i mod 3 != 0" left.
+                while t < n02 {
+                    k += 1;
+                    suffix_array[k] = get_i(t);
+                    t += 1;
+                }
+            }
+        }
+        k += 1;
+    }
+}
+
+fn radix_pass(a: &[usize], b: &mut [usize], keys: &[usize], n: usize, max_symbol: usize) {
+    // Stable sorting "a[0..n]" into "b" by "keys[a[i]]".
+    let mut counts = vec![0; max_symbol + 1];
+    for &i in &a[..n] {
+        counts[keys[i]] += 1;
+    }
+    let mut sum = 0;
+    for count in counts.iter_mut() {
+        let curr = *count;
+        *count = sum;
+        sum += curr;
+    }
+    for &i in &a[..n] {
+        b[counts[keys[i]]] = i;
+        counts[keys[i]] += 1;
+    }
+}
@@ -0,0 +1,143 @@
+use crate::new_suffix_array::InnovativeSuffixArrayBuilder;
+use crate::prefix_tree::monitor::ExecutionInfo;
+use crate::prefix_tree::symbol::Symbol;
+
+// GENERALIZED SUFFIX ARRAY
+// All sequences are concatenated into one collection, each one followed by its own Separator.
+// Separators are unique and smaller than any Symbol, so two Global Suffixes always differ at
+// most at the first Separator met: suffixes never cross sequence boundaries, and equal suffixes
+// of different sequences are sorted by sequence id.
+// From sequences ["ABA", "BA"]:
+//   collection = A B A $0 B A $1
+//   entries    = [(0, 2), (1, 1), (0, 0), (0, 1), (1, 0)]
+//                 "A"     "A"     "ABA"   "BA"    "BA"
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum GeneralizedSymbol<T> {
+    Separator(usize), // Declared first: smaller than any Symbol.
+    Symbol(T),
+}
+impl<T: Symbol> Symbol for GeneralizedSymbol<T> {
+    fn push_label(&self, label: &mut String) {
+        match self {
+            GeneralizedSymbol::Separator(_) => label.push('$'),
+            GeneralizedSymbol::Symbol(symbol) => symbol.push_label(label),
+        }
+    }
+}
+
+pub struct GeneralizedSuffixArrayComputationResults {
+    // Pairs of (sequence id, offset in the sequence), Separators excluded.
+    pub entries: Vec<(usize, usize)>,
+    pub execution_info: ExecutionInfo,
+}
+pub fn compute_generalized_suffix_array<T: Symbol>(
+    sequences: &[&[T]],
+    chunk_size: Option<usize>,
+) -> GeneralizedSuffixArrayComputationResults {
+    let (collection, sequence_indexes) = get_collection_with_separators(sequences);
+
+    // ICFL Factorization and Prefix Tree are applied on the whole collection.
+    let results = InnovativeSuffixArrayBuilder::new(&collection)
+        .chunk_size(chunk_size)
+        .compute();
+
+    let mut entries = Vec::with_capacity(collection.len() - sequences.len());
+    for &gs_index in &results.suffix_array {
+        if let GeneralizedSymbol::Symbol(_) = collection[gs_index] {
+            let sequence_id = sequence_indexes.partition_point(|&i| i <= gs_index) - 1;
+            entries.push((sequence_id, gs_index - sequence_indexes[sequence_id]));
+        }
+    }
+
+    GeneralizedSuffixArrayComputationResults {
+        entries,
+        execution_info: results.execution_info,
+    }
+}
+
+pub fn get_collection_with_separators<T: Copy>(
+    sequences: &[&[T]],
+) -> (Vec<GeneralizedSymbol<T>>, Vec<usize>) {
+    // Returns the collection and where each sequence starts in it.
+    let collection_length = sequences.iter().map(|sequence| sequence.len() + 1).sum();
+    let mut collection = Vec::with_capacity(collection_length);
+    let mut sequence_indexes = Vec::with_capacity(sequences.len());
+    for (sequence_id, sequence) in sequences.iter().enumerate() {
+        sequence_indexes.push(collection.len());
+        for &symbol in *sequence {
+            collection.push(GeneralizedSymbol::Symbol(symbol));
+        }
+        collection.push(GeneralizedSymbol::Separator(sequence_id));
+    }
+    (collection, sequence_indexes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suffix_array::classic_suffix_array::compute_classic_generalized_suffix_array;
+    use crate::test_utils::for_each_random_string;
+    use rand::Rng;
+
+    fn check(sequences: &[&[u8]]) {
+        let sequences_str = sequences
+            .iter()
+            .map(|sequence| std::str::from_utf8(sequence).unwrap())
+            .collect::<Vec<_>>();
+        let expected = compute_classic_generalized_suffix_array(&sequences_str).entries;
+        for chunk_size in [None, Some(2), Some(3), Some(5)] {
+            let entries = compute_generalized_suffix_array(sequences, chunk_size).entries;
+            assert_eq!(
+                entries, expected,
+                "sequences={:?}, chunk_size={:?}",
+                sequences_str, chunk_size
+            );
+        }
+    }
+
+    #[test]
+    fn generalized_suffix_array_on_random_sequences() {
+        // One random string cut in a few sequences, some of them can be empty.
+        for_each_random_string(9, 200, 0..=60, 1..=4, |str, rng| {
+            let num_cuts = rng.random_range(0..=4);
+            let mut cuts = (0..num_cuts)
+                .map(|_| rng.random_range(0..=str.len()))
+                .collect::<Vec<_>>();
+            cuts.sort();
+            let mut sequences = Vec::new();
+            let mut start = 0;
+            for cut in cuts {
+                sequences.push(&str[start..cut]);
+                start = cut;
+            }
+            sequences.push(&str[start..]);
+            check(&sequences);
+        });
+    }
+
+    #[test]
+    fn generalized_suffix_array_edge_cases() {
+        check(&[]);
+        check(&[b""]);
+        check(&[b"", b"", b""]);
+        check(&[b"", b"ABA", b""]);
+        check(&[b"A"]);
+        // Equal sequences and suffixes: sorted by sequence id.
+        check(&[b"ABA", b"ABA", b"BA"]);
+        check(&[b"AAAA", b"AA", b"AAA"]);
+        // A sequence is a prefix of another one: its Separator is smaller than any Symbol.
+        check(&[b"ABAB", b"AB", b"ABABA"]);
+        // Symbols like the "$" label, or even the smallest byte, still come after Separators.
+        check(&[b"A$B", b"$", b"$A$"]);
+        check(&[b"\0A\0", b"A\0", b"\0"]);
+    }
+
+    #[test]
+    fn generalized_suffix_array_example() {
+        let results = compute_generalized_suffix_array(&[b"ABA", b"BA"], None);
+        assert_eq!(
+            results.entries,
+            vec![(0, 2), (1, 1), (0, 0), (0, 1), (1, 0)]
+        );
+    }
+}
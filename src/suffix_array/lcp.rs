@@ -0,0 +1,138 @@
+// LCP ARRAY
+// "lcp_array[i]" is the length of the Longest Common Prefix between the Global Suffixes
+// "suffix_array[i - 1]" and "suffix_array[i]", with "lcp_array[0] = 0".
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LcpStrategy {
+    // Kasai et al. linear pass over the computed Suffix Array.
+    Kasai,
+    // Lower bounds collected while visiting the Prefix Tree in Phase 3, then extended.
+    PrefixTree,
+}
+
+pub fn compute_lcp_array_kasai<T: Eq>(str: &[T], suffix_array: &[usize]) -> Vec<usize> {
+    let str_length = str.len();
+    let mut rank = vec![0; str_length];
+    for (i, &sa_item) in suffix_array.iter().enumerate() {
+        rank[sa_item] = i;
+    }
+    let mut lcp_array = vec![0; suffix_array.len()];
+    let mut h = 0;
+    for gs_index in 0..str_length {
+        if rank[gs_index] > 0 {
+            let prev_gs_index = suffix_array[rank[gs_index] - 1];
+            while gs_index + h < str_length
+                && prev_gs_index + h < str_length
+                && str[gs_index + h] == str[prev_gs_index + h]
+            {
+                h += 1;
+            }
+            lcp_array[rank[gs_index]] = h;
+            // Going to the next Global Suffix loses at most one char of the common prefix.
+            h = h.saturating_sub(1);
+        } else {
+            h = 0;
+        }
+    }
+    lcp_array
+}
+
+pub fn compute_lcp_array_from_bounds<T: Eq>(
+    str: &[T],
+    suffix_array: &[usize],
+    lcp_bounds: &[usize],
+) -> Vec<usize> {
+    // As "compute_lcp_array_kasai", with each bound as a floor: it is a number of chars already
+    // known to be shared with the previous SA item, so the comparison goes on from the greatest
+    // of the bound and the common prefix carried from the previous Global Suffix.
+    let str_length = str.len();
+    let mut rank = vec![0; str_length];
+    for (i, &sa_item) in suffix_array.iter().enumerate() {
+        rank[sa_item] = i;
+    }
+    let mut lcp_array = vec![0; suffix_array.len()];
+    let mut h = 0;
+    for gs_index in 0..str_length {
+        if rank[gs_index] > 0 {
+            let prev_gs_index = suffix_array[rank[gs_index] - 1];
+            h = h.max(lcp_bounds[rank[gs_index]]);
+            while gs_index + h < str_length
+                && prev_gs_index + h < str_length
+                && str[gs_index + h] == str[prev_gs_index + h]
+            {
+                h += 1;
+            }
+            lcp_array[rank[gs_index]] = h;
+            h = h.saturating_sub(1);
+        } else {
+            h = 0;
+        }
+    }
+    lcp_array
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{for_each_random_string, get_repetitive_strings};
+    use crate::InnovativeSuffixArrayBuilder;
+
+    fn assert_both_strategies_as_naive(str: &[u8]) {
+        let mut naive_suffix_array = (0..str.len()).collect::<Vec<_>>();
+        naive_suffix_array.sort_by_key(|&gs_index| &str[gs_index..]);
+        let naive_lcp_array = (0..str.len())
+            .map(|i| match i {
+                0 => 0,
+                _ => str[naive_suffix_array[i - 1]..]
+                    .iter()
+                    .zip(&str[naive_suffix_array[i]..])
+                    .take_while(|(a, b)| a == b)
+                    .count(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            compute_lcp_array_kasai(str, &naive_suffix_array),
+            naive_lcp_array
+        );
+        for chunk_size in [None, Some(2), Some(6)] {
+            for lcp_strategy in [LcpStrategy::Kasai, LcpStrategy::PrefixTree] {
+                let results = InnovativeSuffixArrayBuilder::new(str)
+                    .chunk_size(chunk_size)
+                    .lcp(Some(lcp_strategy))
+                    .compute();
+                assert_eq!(
+                    results.lcp_array.unwrap(),
+                    naive_lcp_array,
+                    "str={:?}, chunk_size={chunk_size:?}, {lcp_strategy:?}",
+                    String::from_utf8_lossy(str)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lcp_strategies_on_random_strings() {
+        for_each_random_string(4, 100, 1..=100, 1..=4, |str, _| {
+            assert_both_strategies_as_naive(str);
+        });
+    }
+
+    #[test]
+    fn lcp_strategies_on_repetitive_strings() {
+        for str in get_repetitive_strings(40) {
+            assert_both_strategies_as_naive(&str);
+        }
+        for str in [vec![b'A'; 500], b"AC".repeat(250), b"AAC".repeat(200)] {
+            assert_both_strategies_as_naive(&str);
+        }
+    }
+
+    #[test]
+    fn lcp_from_bounds_is_linear_on_repetitive_strings() {
+        // Quadratic extensions would take minutes here.
+        let str = vec![b'A'; 200_000];
+        let suffix_array = (0..str.len()).rev().collect::<Vec<_>>();
+        let lcp_array = compute_lcp_array_from_bounds(&str, &suffix_array, &vec![0; str.len()]);
+        assert_eq!(lcp_array, compute_lcp_array_kasai(&str, &suffix_array));
+    }
+}
@@ -1,14 +1,37 @@
 use std::fs::{create_dir_all, File};
-use std::io::Write;
+use std::io::{BufRead, BufReader, Write};
 
 pub fn make_sure_directory_exist(folder_path: String) {
     create_dir_all(folder_path).unwrap();
 }
-pub fn log_suffix_array(sa: &Vec<usize>, filepath: String) {
+pub fn log_suffix_array(sa: &[usize], filepath: String) {
     let mut file = File::create(filepath).expect("Unable to create file");
     for sa_item in sa {
-        file.write(format!("{}\n", sa_item).as_bytes())
+        file.write_all(format!("{}\n", sa_item).as_bytes())
             .expect("Unable to write");
     }
     file.flush().expect("Unable to flush file");
 }
+pub fn read_suffix_array(filepath: String) -> Result<Vec<usize>, String> {
+    // Reads files written by "log_suffix_array", one index per line.
+    let file = File::open(&filepath)
+        .map_err(|e| format!("Unable to read Suffix Array file \"{}\": {}", filepath, e))?;
+    let mut sa = Vec::new();
+    for (i_line, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("Unable to read \"{}\": {}", filepath, e))?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let sa_item = line.parse().map_err(|_| {
+            format!(
+                "Invalid index \"{}\" at line {} of \"{}\"",
+                line,
+                i_line + 1,
+                filepath
+            )
+        })?;
+        sa.push(sa_item);
+    }
+    Ok(sa)
+}
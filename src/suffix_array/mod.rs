@@ -1,3 +1,11 @@
+pub mod baseline;
 pub mod classic_suffix_array;
+pub mod dc3;
+pub mod generalized_suffix_array;
+pub mod lcp;
 pub mod logger;
+pub mod prefix_doubling;
+pub mod sais;
 pub mod sorter;
+pub mod suffix_array_index;
+pub mod verifier;
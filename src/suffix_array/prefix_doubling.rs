@@ -0,0 +1,59 @@
+// Prefix Doubling (Larsson, Sadakane): after step "h" suffixes are sorted by their first "2h"
+// symbols. Only groups not yet sorted are refined, and the rank of a group is the index of its
+// last suffix, so that ranks updated during a step remain consistent.
+// NOTE: Groups are sorted with the standard sort instead of the original ternary-split quicksort.
+pub fn compute_suffix_array_prefix_doubling(str: &[u8]) -> Vec<usize> {
+    let n = str.len();
+    let mut suffix_array = (0..n).collect::<Vec<_>>();
+    suffix_array.sort_by_key(|&i| str[i]);
+
+    // Initial groups by first symbol.
+    let mut ranks = vec![0; n];
+    let mut unsorted_groups = Vec::new();
+    let mut group_start = 0;
+    for k in 0..n {
+        if k == n - 1 || str[suffix_array[k]] != str[suffix_array[k + 1]] {
+            for &i in &suffix_array[group_start..=k] {
+                ranks[i] = k;
+            }
+            if k > group_start {
+                unsorted_groups.push((group_start, k + 1));
+            }
+            group_start = k + 1;
+        }
+    }
+
+    let mut h = 1;
+    let mut keyed_group = Vec::new();
+    while !unsorted_groups.is_empty() {
+        let mut next_unsorted_groups = Vec::new();
+        for (start, end) in unsorted_groups {
+            // Keys are computed before updating ranks, since the suffixes of the group could be
+            // each other's key. Suffixes shorter than "h" come first.
+            keyed_group.clear();
+            for &i in &suffix_array[start..end] {
+                let key = if i + h < n { ranks[i + h] + 1 } else { 0 };
+                keyed_group.push((key, i));
+            }
+            keyed_group.sort_unstable();
+
+            let mut subgroup_start = 0;
+            for k in 0..keyed_group.len() {
+                suffix_array[start + k] = keyed_group[k].1;
+                if k == keyed_group.len() - 1 || keyed_group[k].0 != keyed_group[k + 1].0 {
+                    for &(_, i) in &keyed_group[subgroup_start..=k] {
+                        ranks[i] = start + k;
+                    }
+                    if k > subgroup_start {
+                        next_unsorted_groups.push((start + subgroup_start, start + k + 1));
+                    }
+                    subgroup_start = k + 1;
+                }
+            }
+        }
+        unsorted_groups = next_unsorted_groups;
+        h *= 2;
+    }
+
+    suffix_array
+}
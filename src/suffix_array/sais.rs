@@ -0,0 +1,162 @@
+const EMPTY: usize = usize::MAX;
+
+// SA-IS (Nong, Zhang, Chan): Induced Sorting of LMS Substrings, linear time.
+pub fn compute_suffix_array_sais(str: &[u8]) -> Vec<usize> {
+    if str.is_empty() {
+        return Vec::new();
+    }
+    // Symbols shifted by one, so that "0" is the unique smallest sentinel.
+    let mut s = Vec::with_capacity(str.len() + 1);
+    for &byte in str {
+        s.push(byte as usize + 1);
+    }
+    s.push(0);
+    let mut suffix_array = sais(&s, 257);
+    // The sentinel suffix is always the first.
+    suffix_array.remove(0);
+    suffix_array
+}
+
+fn sais(s: &[usize], alphabet_size: usize) -> Vec<usize> {
+    // Requires "s" to end with a sentinel, the unique smallest symbol.
+    let n = s.len();
+    if n == 1 {
+        return vec![0];
+    }
+
+    // Suffix Types: S-type ("true") or L-type ("false").
+    let mut is_s_type = vec![false; n];
+    is_s_type[n - 1] = true;
+    for i in (0..n - 1).rev() {
+        is_s_type[i] = s[i] < s[i + 1] || (s[i] == s[i + 1] && is_s_type[i + 1]);
+    }
+    let is_lms = |i: usize| i > 0 && is_s_type[i] && !is_s_type[i - 1];
+
+    let mut bucket_sizes = vec![0; alphabet_size];
+    for &symbol in s {
+        bucket_sizes[symbol] += 1;
+    }
+
+    // Step 1: sorting LMS Substrings by inducing from LMS positions in any order.
+    let mut suffix_array = vec![EMPTY; n];
+    let mut tails = get_bucket_tails(&bucket_sizes);
+    for i in 1..n {
+        if is_lms(i) {
+            tails[s[i]] -= 1;
+            suffix_array[tails[s[i]]] = i;
+        }
+    }
+    induce_sort(s, &is_s_type, &bucket_sizes, &mut suffix_array);
+
+    // Step 2: naming LMS Substrings, equal ones get the same name.
+    let mut names = vec![EMPTY; n];
+    let mut curr_name = 0;
+    let mut prev_lms = None;
+    for &i in &suffix_array {
+        if !is_lms(i) {
+            continue;
+        }
+        if let Some(prev_lms) = prev_lms {
+            if !are_lms_substrings_equal(s, &is_s_type, prev_lms, i) {
+                curr_name += 1;
+            }
+        }
+        names[i] = curr_name;
+        prev_lms = Some(i);
+    }
+    let lms_positions = (1..n).filter(|&i| is_lms(i)).collect::<Vec<_>>();
+    let reduced_str = lms_positions.iter().map(|&i| names[i]).collect::<Vec<_>>();
+    let num_names = curr_name + 1;
+
+    // Step 3: sorting LMS Suffixes, recursively if names are not unique.
+    let reduced_suffix_array = if num_names == reduced_str.len() {
+        let mut reduced_suffix_array = vec![0; reduced_str.len()];
+        for (i, &name) in reduced_str.iter().enumerate() {
+            reduced_suffix_array[name] = i;
+        }
+        reduced_suffix_array
+    } else {
+        sais(&reduced_str, num_names)
+    };
+
+    // Step 4: inducing all suffixes from the sorted LMS Suffixes.
+    suffix_array.fill(EMPTY);
+    let mut tails = get_bucket_tails(&bucket_sizes);
+    for &reduced_i in reduced_suffix_array.iter().rev() {
+        let i = lms_positions[reduced_i];
+        tails[s[i]] -= 1;
+        suffix_array[tails[s[i]]] = i;
+    }
+    induce_sort(s, &is_s_type, &bucket_sizes, &mut suffix_array);
+
+    suffix_array
+}
+
+fn induce_sort(
+    s: &[usize],
+    is_s_type: &[bool],
+    bucket_sizes: &[usize],
+    suffix_array: &mut [usize],
+) {
+    let n = s.len();
+    // L-type suffixes, from left to right.
+    let mut heads = get_bucket_heads(bucket_sizes);
+    for k in 0..n {
+        let i = suffix_array[k];
+        if i != EMPTY && i > 0 && !is_s_type[i - 1] {
+            let symbol = s[i - 1];
+            suffix_array[heads[symbol]] = i - 1;
+            heads[symbol] += 1;
+        }
+    }
+    // S-type suffixes, from right to left.
+    let mut tails = get_bucket_tails(bucket_sizes);
+    for k in (0..n).rev() {
+        let i = suffix_array[k];
+        if i != EMPTY && i > 0 && is_s_type[i - 1] {
+            let symbol = s[i - 1];
+            tails[symbol] -= 1;
+            suffix_array[tails[symbol]] = i - 1;
+        }
+    }
+}
+
+fn are_lms_substrings_equal(s: &[usize], is_s_type: &[bool], a: usize, b: usize) -> bool {
+    let n = s.len();
+    if a == n - 1 || b == n - 1 {
+        // The sentinel is unique.
+        return a == b;
+    }
+    let is_lms = |i: usize| i > 0 && is_s_type[i] && !is_s_type[i - 1];
+    let mut d = 0;
+    loop {
+        if s[a + d] != s[b + d] || is_s_type[a + d] != is_s_type[b + d] {
+            return false;
+        }
+        if d > 0 && (is_lms(a + d) || is_lms(b + d)) {
+            return is_lms(a + d) && is_lms(b + d);
+        }
+        d += 1;
+    }
+}
+
+fn get_bucket_heads(bucket_sizes: &[usize]) -> Vec<usize> {
+    let mut heads = Vec::with_capacity(bucket_sizes.len());
+    let mut sum = 0;
+    for &size in bucket_sizes {
+        heads.push(sum);
+        sum += size;
+    }
+    heads
+}
+
+fn get_bucket_tails(bucket_sizes: &[usize]) -> Vec<usize> {
+    // Exclusive ends.
+    let mut tails = Vec::with_capacity(bucket_sizes.len());
+    let mut sum = 0;
+    for &size in bucket_sizes {
+        sum += size;
+        tails.push(sum);
+    }
+    tails
+}
@@ -0,0 +1,268 @@
+use crate::new_suffix_array::InnovativeSuffixArrayBuilder;
+use crate::prefix_tree::symbol::Symbol;
+use crate::suffix_array::lcp::LcpStrategy;
+use std::cmp::Ordering;
+use std::ops::Range;
+
+// PATTERN SEARCH OVER THE SUFFIX ARRAY
+// Binary Search for the window of SA items whose Global Suffix starts with the pattern. With
+// LCP-LR (Manber-Myers) each step restarts comparing from the chars already known to match, so
+// a search costs O(m + log n) chars compared instead of O(m log n).
+pub struct SuffixArrayIndex<'a, T> {
+    str: &'a [T],
+    suffix_array: Vec<usize>,
+    lcp_lr: Option<LcpLr>,
+}
+struct LcpLr {
+    // For the Binary Search interval (L, R) whose middle is M:
+    // "left[M]" = LCP(SA[L], SA[M]) and "right[M]" = LCP(SA[M], SA[R]).
+    left: Vec<usize>,
+    right: Vec<usize>,
+}
+impl<'a, T: Symbol> SuffixArrayIndex<'a, T> {
+    pub fn new(str: &'a [T], chunk_size: Option<usize>, use_lcp_lr: bool) -> Self {
+        if str.is_empty() {
+            return Self::from_suffix_array(str, Vec::new());
+        }
+        let results = InnovativeSuffixArrayBuilder::new(str)
+            .chunk_size(chunk_size)
+            .lcp(if use_lcp_lr {
+                Some(LcpStrategy::PrefixTree)
+            } else {
+                None
+            })
+            .compute();
+        let index = Self::from_suffix_array(str, results.suffix_array);
+        if let Some(lcp_array) = results.lcp_array {
+            index.with_lcp_lr(&lcp_array)
+        } else {
+            index
+        }
+    }
+    pub fn from_suffix_array(str: &'a [T], suffix_array: Vec<usize>) -> Self {
+        Self {
+            str,
+            suffix_array,
+            lcp_lr: None,
+        }
+    }
+    pub fn with_lcp_lr(mut self, lcp_array: &[usize]) -> Self {
+        let n = self.suffix_array.len();
+        let mut lcp_lr = LcpLr {
+            left: vec![0; n],
+            right: vec![0; n],
+        };
+        if n > 1 {
+            fill_lcp_lr(&mut lcp_lr, lcp_array, 0, n - 1);
+        }
+        self.lcp_lr = Some(lcp_lr);
+        self
+    }
+    pub fn get_suffix_array(&self) -> &Vec<usize> {
+        &self.suffix_array
+    }
+
+    pub fn find_range(&self, pattern: &[T]) -> Range<usize> {
+        let from = self.search(pattern, false);
+        let to = self.search(pattern, true);
+        from..to
+    }
+    pub fn count(&self, pattern: &[T]) -> usize {
+        self.find_range(pattern).len()
+    }
+    pub fn occurrences(&self, pattern: &[T]) -> Vec<usize> {
+        let mut result = self.suffix_array[self.find_range(pattern)].to_vec();
+        result.sort();
+        result
+    }
+
+    fn search(&self, pattern: &[T], after_matches: bool) -> usize {
+        // First SA item that is not "on the left" of the pattern. Being on the left means being
+        // smaller than the pattern, or (if "after_matches") also starting with it.
+        let n = self.suffix_array.len();
+        if n == 0 {
+            return 0;
+        }
+        let is_on_the_left = |ordering: Ordering| {
+            ordering == Ordering::Greater || (after_matches && ordering == Ordering::Equal)
+        };
+        let (mut l, ordering) = self.compare(pattern, self.suffix_array[0], 0);
+        if !is_on_the_left(ordering) {
+            return 0;
+        }
+        let (mut r, ordering) = self.compare(pattern, self.suffix_array[n - 1], 0);
+        if is_on_the_left(ordering) {
+            return n;
+        }
+
+        // Invariant: SA[L] is on the left, SA[R] is not; "l" and "r" are their LCPs with the
+        // pattern.
+        let mut i_left = 0;
+        let mut i_right = n - 1;
+        while i_right - i_left > 1 {
+            let i_mid = (i_left + i_right) / 2;
+            let mid_goes_left = if let Some(lcp_lr) = &self.lcp_lr {
+                if l >= r {
+                    let lcp_left_mid = lcp_lr.left[i_mid];
+                    if lcp_left_mid > l {
+                        // SA[M] goes on as SA[L] beyond the pattern mismatch.
+                        true
+                    } else if lcp_left_mid < l {
+                        // SA[M] is greater than SA[L] where it still matches the pattern.
+                        r = lcp_left_mid;
+                        false
+                    } else {
+                        let (h, ordering) = self.compare(pattern, self.suffix_array[i_mid], l);
+                        self.update_lcps(is_on_the_left(ordering), h, &mut l, &mut r)
+                    }
+                } else {
+                    let lcp_mid_right = lcp_lr.right[i_mid];
+                    if lcp_mid_right > r {
+                        false
+                    } else if lcp_mid_right < r {
+                        l = lcp_mid_right;
+                        true
+                    } else {
+                        let (h, ordering) = self.compare(pattern, self.suffix_array[i_mid], r);
+                        self.update_lcps(is_on_the_left(ordering), h, &mut l, &mut r)
+                    }
+                }
+            } else {
+                let (h, ordering) = self.compare(pattern, self.suffix_array[i_mid], l.min(r));
+                self.update_lcps(is_on_the_left(ordering), h, &mut l, &mut r)
+            };
+            if mid_goes_left {
+                i_left = i_mid;
+            } else {
+                i_right = i_mid;
+            }
+        }
+        i_right
+    }
+    fn update_lcps(&self, goes_left: bool, h: usize, l: &mut usize, r: &mut usize) -> bool {
+        if goes_left {
+            *l = h;
+        } else {
+            *r = h;
+        }
+        goes_left
+    }
+    fn compare(&self, pattern: &[T], gs_index: usize, known_lcp: usize) -> (usize, Ordering) {
+        // Compares the pattern with the Global Suffix, knowing the first "known_lcp" chars match.
+        // Equal means that the Global Suffix starts with the pattern.
+        let gs = &self.str[gs_index..];
+        let mut h = known_lcp;
+        while h < pattern.len() && h < gs.len() {
+            if pattern[h] != gs[h] {
+                return (h, pattern[h].cmp(&gs[h]));
+            }
+            h += 1;
+        }
+        if h == pattern.len() {
+            (h, Ordering::Equal)
+        } else {
+            (h, Ordering::Greater)
+        }
+    }
+}
+
+fn fill_lcp_lr(lcp_lr: &mut LcpLr, lcp_array: &[usize], i_left: usize, i_right: usize) -> usize {
+    // Returns LCP(SA[L], SA[R]), the minimum of the LCP Array in (L, R].
+    if i_right - i_left == 1 {
+        return lcp_array[i_right];
+    }
+    let i_mid = (i_left + i_right) / 2;
+    let lcp_left_mid = fill_lcp_lr(lcp_lr, lcp_array, i_left, i_mid);
+    let lcp_mid_right = fill_lcp_lr(lcp_lr, lcp_array, i_mid, i_right);
+    lcp_lr.left[i_mid] = lcp_left_mid;
+    lcp_lr.right[i_mid] = lcp_mid_right;
+    lcp_left_mid.min(lcp_mid_right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::for_each_random_string;
+    use rand::Rng;
+
+    fn get_occurrences_via_naive_scan(str: &[u8], pattern: &[u8]) -> Vec<usize> {
+        // Every position for the empty pattern, as the SA has no empty Global Suffix.
+        (0..str.len())
+            .filter(|&i| str[i..].starts_with(pattern))
+            .collect()
+    }
+
+    fn assert_same_as_naive_scan(str: &[u8], patterns: &[&[u8]], chunk_size: Option<usize>) {
+        for use_lcp_lr in [false, true] {
+            let index = SuffixArrayIndex::new(str, chunk_size, use_lcp_lr);
+            for &pattern in patterns {
+                let expected = get_occurrences_via_naive_scan(str, pattern);
+                assert_eq!(
+                    index.occurrences(pattern),
+                    expected,
+                    "str={:?}, pattern={:?}, chunk_size={chunk_size:?}, lcp_lr={use_lcp_lr}",
+                    String::from_utf8_lossy(str),
+                    String::from_utf8_lossy(pattern),
+                );
+                assert_eq!(index.count(pattern), expected.len());
+            }
+        }
+    }
+
+    #[test]
+    fn search_edge_cases() {
+        let str = b"ABRACADABRA";
+        let patterns: [&[u8]; 7] = [
+            b"",             // Empty.
+            b"ABRACADABRAA", // Longer than the text.
+            b"ABRACADABRA",  // The whole text.
+            b"ABD",          // Not found, between two suffixes.
+            b"Z",            // Not found, after all suffixes.
+            b"0",            // Not found, before all suffixes.
+            b"A",            // Many matches.
+        ];
+        for chunk_size in [None, Some(1), Some(3)] {
+            assert_same_as_naive_scan(str, &patterns, chunk_size);
+        }
+    }
+
+    #[test]
+    fn search_overlapping_matches() {
+        let str = b"AAAAAAAAAABAAABABABA";
+        let patterns: [&[u8]; 5] = [b"AA", b"AAA", b"ABA", b"BAB", b"ABABA"];
+        for chunk_size in [None, Some(2), Some(4)] {
+            assert_same_as_naive_scan(str, &patterns, chunk_size);
+        }
+    }
+
+    #[test]
+    fn search_empty_text() {
+        let index = SuffixArrayIndex::new(b"", None, true);
+        assert!(index.occurrences(b"").is_empty());
+        assert!(index.occurrences(b"A").is_empty());
+    }
+
+    #[test]
+    fn search_random_texts() {
+        for_each_random_string(7, 200, 1..=80, 1..=4, |str, rng| {
+            // Half of the patterns are taken from the text, the others are random ones (also
+            // with chars not in the text).
+            let patterns = (0..20)
+                .map(|i_pattern| {
+                    let pattern_length = rng.random_range(1..=6);
+                    if i_pattern % 2 == 0 && str.len() > pattern_length {
+                        let from = rng.random_range(0..str.len() - pattern_length);
+                        str[from..from + pattern_length].to_vec()
+                    } else {
+                        (0..pattern_length)
+                            .map(|_| b'A' + rng.random_range(0..5))
+                            .collect()
+                    }
+                })
+                .collect::<Vec<_>>();
+            let patterns = patterns.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let chunk_size = rng.random_range(1..=6);
+            assert_same_as_naive_scan(str, &patterns, Some(chunk_size));
+        });
+    }
+}
@@ -0,0 +1,274 @@
+use std::fmt::{Display, Formatter};
+
+// Linear verification of a Suffix Array against its text, without a reference Suffix Array
+// (Burkhardt, Kärkkäinen): "sa" is the Suffix Array of "str" iff it is a permutation of
+// "0..str.len()" and, for each pair of adjacent suffixes "i = sa[k]" and "j = sa[k+1]", either
+// "str[i] < str[j]" or "str[i] == str[j]" and suffix "i+1" comes before suffix "j+1".
+pub fn verify_suffix_array<T: Ord>(
+    str: &[T],
+    suffix_array: &[usize],
+) -> SuffixArrayVerificationReport {
+    let violation = find_first_violation(str, suffix_array);
+    SuffixArrayVerificationReport {
+        text_length: str.len(),
+        suffix_array_length: suffix_array.len(),
+        violation,
+    }
+}
+
+fn find_first_violation<T: Ord>(str: &[T], suffix_array: &[usize]) -> Option<SuffixArrayViolation> {
+    let n = str.len();
+    if suffix_array.len() != n {
+        return Some(SuffixArrayViolation::WrongLength {
+            expected: n,
+            found: suffix_array.len(),
+        });
+    }
+
+    // Permutation check, while building the Inverse Suffix Array.
+    // Rank "0" is for the empty suffix "n", so ranks of suffixes are shifted by one.
+    let mut ranks = vec![0; n + 1];
+    for (k, &i) in suffix_array.iter().enumerate() {
+        if i >= n {
+            return Some(SuffixArrayViolation::IndexOutOfBounds {
+                position: k,
+                suffix_index: i,
+            });
+        }
+        if ranks[i] != 0 {
+            return Some(SuffixArrayViolation::DuplicateIndex {
+                position: k,
+                first_position: ranks[i] - 1,
+                suffix_index: i,
+            });
+        }
+        ranks[i] = k + 1;
+    }
+
+    // Order check on adjacent suffixes.
+    for k in 1..n {
+        let i = suffix_array[k - 1];
+        let j = suffix_array[k];
+        let is_ordered = str[i] < str[j] || (str[i] == str[j] && ranks[i + 1] < ranks[j + 1]);
+        if !is_ordered {
+            return Some(SuffixArrayViolation::WrongOrder {
+                position: k - 1,
+                suffix_index: i,
+                next_suffix_index: j,
+            });
+        }
+    }
+
+    None
+}
+
+pub struct SuffixArrayVerificationReport {
+    pub text_length: usize,
+    pub suffix_array_length: usize,
+    pub violation: Option<SuffixArrayViolation>, // The first one found, if any.
+}
+impl SuffixArrayVerificationReport {
+    pub fn is_ok(&self) -> bool {
+        self.violation.is_none()
+    }
+}
+impl Display for SuffixArrayVerificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.violation {
+            None => write!(
+                f,
+                "Suffix Array of {} elements is correct",
+                self.suffix_array_length
+            ),
+            Some(violation) => write!(f, "Suffix Array is WRONG: {}", violation),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SuffixArrayViolation {
+    WrongLength {
+        expected: usize,
+        found: usize,
+    },
+    IndexOutOfBounds {
+        position: usize,
+        suffix_index: usize,
+    },
+    DuplicateIndex {
+        position: usize,
+        first_position: usize,
+        suffix_index: usize,
+    },
+    WrongOrder {
+        position: usize, // Suffixes at "position" and "position + 1".
+        suffix_index: usize,
+        next_suffix_index: usize,
+    },
+}
+impl Display for SuffixArrayViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuffixArrayViolation::WrongLength { expected, found } => {
+                write!(f, "length should be {} but is {}", expected, found)
+            }
+            SuffixArrayViolation::IndexOutOfBounds {
+                position,
+                suffix_index,
+            } => write!(
+                f,
+                "element [{}] is \"{}\", out of the text",
+                position, suffix_index
+            ),
+            SuffixArrayViolation::DuplicateIndex {
+                position,
+                first_position,
+                suffix_index,
+            } => write!(
+                f,
+                "element [{}] is \"{}\", already in element [{}]",
+                position, suffix_index, first_position
+            ),
+            SuffixArrayViolation::WrongOrder {
+                position,
+                suffix_index,
+                next_suffix_index,
+            } => write!(
+                f,
+                "elements [{}] and [{}] are \"{}\" and \"{}\", but these suffixes are not sorted",
+                position,
+                position + 1,
+                suffix_index,
+                next_suffix_index
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{for_each_random_string, get_repetitive_strings};
+    use rand::Rng;
+
+    fn get_naive_suffix_array(str: &[u8]) -> Vec<usize> {
+        let mut suffix_array = (0..str.len()).collect::<Vec<_>>();
+        suffix_array.sort_by(|&a, &b| str[a..].cmp(&str[b..]));
+        suffix_array
+    }
+    fn get_violation(str: &[u8], suffix_array: &[usize]) -> Option<SuffixArrayViolation> {
+        verify_suffix_array(str, suffix_array).violation
+    }
+
+    #[test]
+    fn accept_correct_suffix_arrays() {
+        assert!(verify_suffix_array(b"banana", &[5, 3, 1, 0, 4, 2]).is_ok());
+        assert!(verify_suffix_array(b"", &[]).is_ok());
+        for str in get_repetitive_strings(40) {
+            assert!(verify_suffix_array(&str, &get_naive_suffix_array(&str)).is_ok());
+        }
+    }
+
+    #[test]
+    fn reject_swapped_pair() {
+        assert_eq!(
+            get_violation(b"banana", &[5, 1, 3, 0, 4, 2]),
+            Some(SuffixArrayViolation::WrongOrder {
+                position: 1,
+                suffix_index: 1,
+                next_suffix_index: 3,
+            })
+        );
+        // Also far apart: the first pair out of order is reported.
+        assert_eq!(
+            get_violation(b"banana", &[5, 3, 2, 0, 4, 1]),
+            Some(SuffixArrayViolation::WrongOrder {
+                position: 2,
+                suffix_index: 2,
+                next_suffix_index: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn reject_duplicate_index() {
+        assert_eq!(
+            get_violation(b"banana", &[5, 3, 1, 0, 3, 2]),
+            Some(SuffixArrayViolation::DuplicateIndex {
+                position: 4,
+                first_position: 1,
+                suffix_index: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn reject_missing_index() {
+        // Replaced by an index out of the text, or just left out.
+        assert_eq!(
+            get_violation(b"banana", &[5, 3, 1, 0, 6, 2]),
+            Some(SuffixArrayViolation::IndexOutOfBounds {
+                position: 4,
+                suffix_index: 6,
+            })
+        );
+        assert_eq!(
+            get_violation(b"banana", &[5, 3, 1, 0, 2]),
+            Some(SuffixArrayViolation::WrongLength {
+                expected: 6,
+                found: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn reject_wrong_length() {
+        assert_eq!(
+            get_violation(b"banana", &[5, 3, 1, 0, 4, 2, 6]),
+            Some(SuffixArrayViolation::WrongLength {
+                expected: 6,
+                found: 7,
+            })
+        );
+        assert_eq!(
+            get_violation(b"", &[0]),
+            Some(SuffixArrayViolation::WrongLength {
+                expected: 0,
+                found: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn reject_corrupted_random_suffix_arrays() {
+        // The Suffix Array is unique: any change to a correct one must be rejected.
+        for_each_random_string(14, 300, 2..=60, 1..=4, |str, rng| {
+            let suffix_array = get_naive_suffix_array(str);
+            assert!(verify_suffix_array(str, &suffix_array).is_ok());
+            let n = suffix_array.len();
+            let a = rng.random_range(0..n);
+            let b = (a + rng.random_range(1..n)) % n;
+
+            let mut swapped = suffix_array.clone();
+            swapped.swap(a, b);
+            assert!(matches!(
+                get_violation(str, &swapped),
+                Some(SuffixArrayViolation::WrongOrder { .. })
+            ));
+
+            let mut duplicated = suffix_array.clone();
+            duplicated[a] = suffix_array[b];
+            assert!(matches!(
+                get_violation(str, &duplicated),
+                Some(SuffixArrayViolation::DuplicateIndex { .. })
+            ));
+
+            let mut missing = suffix_array.clone();
+            missing.remove(a);
+            assert!(matches!(
+                get_violation(str, &missing),
+                Some(SuffixArrayViolation::WrongLength { .. })
+            ));
+        });
+    }
+}
@@ -1,125 +1,465 @@
+use crate::factorization::custom_factorization::Chunking;
+use crate::factorization::stats::FactorizationStats;
 use crate::files::fasta::get_fasta_content;
-use crate::files::paths::get_path_in_generated_folder;
+use crate::files::json::dump_json_in_file;
+use crate::files::paths::{
+    get_path_for_project_folder, get_path_for_project_suite_stats_file_json,
+};
 use crate::new_suffix_array::compute_innovative_suffix_array;
-use crate::plot::plot::draw_plot_from_monitor;
-use crate::suffix_array::classic_suffix_array::compute_classic_suffix_array;
+use crate::plot::plot::{draw_memory_plot, draw_plot_with_error_bars};
+use crate::prefix_tree::log_execution_outcome::ExecutionOutcomeFileFormat;
+use crate::prefix_tree::log_memory_usage::MemoryUsageFileFormat;
+use crate::suffix_array::baseline::compute_baseline_suffix_array;
+use crate::suffix_array::logger::make_sure_directory_exist;
+use crate::suffix_array::verifier::verify_suffix_array;
+use crate::suite_config::SuiteConfig;
+use crate::suite_stats::{
+    compute_duration_stats, DurationStats, PlotStatistic, SuiteAutoChunkSizeStatsFileFormat,
+    SuiteBaselineStatsFileFormat, SuiteChunkSizeStatsFileFormat, SuiteStatsFileFormat,
+};
 use std::time::Duration;
 
 // SUITE COMPLETE FOR CLASSIC VS INNOVATIVE COMPUTATION
-pub fn full_suite(
-    fasta_file_name: &str,
-    chunk_size_vec: &Vec<Option<usize>>,
-    max_duration_in_micros: u32,
-    num_attempts: usize,
-    log_execution: bool,
-    log_fact: bool,
-    log_trees_and_suffix_array: bool,
-    draw_plot: bool,
-) {
-    println!("\n\nCOMPUTING SUITE ON FILE: \"{}\"\n", fasta_file_name);
+pub fn full_suite(config: &SuiteConfig) {
+    let fasta_file_name = config.fasta_file_name.as_str();
+    let chunk_size_vec = &config.chunk_size_vec;
+    let num_attempts = config.num_attempts;
+    println!(
+        "\n\nCOMPUTING SUITE ON FILE: \"{}\" (PHASE 2 THREADS: {}, PHASE 3 THREADS: {})\n",
+        fasta_file_name, config.tree_num_threads, config.num_threads
+    );
 
     // READING FILE
-    let str = &get_fasta_content(get_path_in_generated_folder(fasta_file_name));
+    let str = &get_fasta_content(config.input_file.clone(), &config.fasta_selection);
 
-    // SUMS FOR MEAN
-    let mut sum_classic_micros = 0;
-    let mut sum_innovative_micros_vec = Vec::new();
-    for _ in 0..chunk_size_vec.len() {
-        sum_innovative_micros_vec.push((0, 0, 0));
+    // SAMPLES FOR STATISTICS
+    let num_warm_up_attempts = config.num_warm_up_attempts;
+    let baselines = &config.baselines;
+    let mut baseline_samples_vec = Vec::new();
+    for _ in 0..baselines.len() {
+        baseline_samples_vec.push(Vec::with_capacity(num_attempts));
+    }
+    // Innovative runs: every Chunk Size with every Variant (Factorization and Tree Layout).
+    let variants = config.get_variants();
+    let mut innovative_runs = Vec::new();
+    for &chunk_size in chunk_size_vec {
+        for &variant in &variants {
+            innovative_runs.push((chunk_size, variant));
+        }
     }
+    let mut innovative_samples_vec = Vec::new();
+    for _ in 0..innovative_runs.len() {
+        innovative_samples_vec.push(PhaseSamples::new(num_attempts));
+    }
+    // Memory Usage, Execution Outcome and Factorization Statistics do not change between attempts,
+    // the last ones are kept.
+    let mut memory_usage_vec = vec![None; innovative_runs.len()];
+    let mut execution_outcome_vec = vec![None; innovative_runs.len()];
+    let mut factorization_stats_vec = vec![None; innovative_runs.len()];
+    // Runs with Automatic Chunk Size: one per Variant.
+    let mut auto_samples_vec = Vec::new();
+    for _ in 0..variants.len() {
+        auto_samples_vec.push(PhaseSamples::new(num_attempts));
+    }
+    let mut auto_selected_chunk_size_vec = vec![None; variants.len()];
+    let mut auto_factorization_stats_vec = vec![None; variants.len()];
 
-    // MULTIPLE ATTEMPTS
-    for i_attempt in 1..=num_attempts {
-        println!(" > NUM ATTEMPT: {}/{}", i_attempt, num_attempts);
+    // MULTIPLE ATTEMPTS (AFTER WARM-UP ONES)
+    for i_attempt in 1..=num_warm_up_attempts + num_attempts {
+        let is_warm_up = i_attempt <= num_warm_up_attempts;
+        if is_warm_up {
+            println!(" > WARM-UP ATTEMPT: {}/{}", i_attempt, num_warm_up_attempts);
+        } else {
+            println!(
+                " > NUM ATTEMPT: {}/{}",
+                i_attempt - num_warm_up_attempts,
+                num_attempts
+            );
+        }
 
-        // CLASSIC SUFFIX ARRAY
-        let classic_suffix_array_computation = compute_classic_suffix_array(str);
-        let classic_suffix_array = classic_suffix_array_computation.suffix_array;
-        sum_classic_micros += classic_suffix_array_computation.duration.as_micros() as u64;
+        // BASELINE SUFFIX ARRAYS
+        for (i, &baseline) in baselines.iter().enumerate() {
+            let baseline_suffix_array_computation = compute_baseline_suffix_array(baseline, str);
+            if !is_warm_up {
+                baseline_samples_vec[i]
+                    .push(baseline_suffix_array_computation.duration.as_micros() as u64);
+            }
+            let report = verify_suffix_array(
+                str.as_bytes(),
+                &baseline_suffix_array_computation.suffix_array,
+            );
+            if !report.is_ok() {
+                println!("Baseline \"{}\": {}", baseline.get_label(), report);
+            }
+        }
 
         // INNOVATIVE SUFFIX ARRAY
-        let mut i = 0;
-        for &chunk_size in chunk_size_vec {
+        for (i, &(chunk_size, variant)) in innovative_runs.iter().enumerate() {
             let innovative_suffix_array_computation = compute_innovative_suffix_array(
+                &config.get_results_folder(&variant),
                 fasta_file_name,
-                str,
-                chunk_size,
-                log_execution,
-                log_fact,
-                log_trees_and_suffix_array,
+                str.as_bytes(),
+                Chunking {
+                    chunk_size,
+                    threshold: config.chunk_threshold,
+                    split_policy: variant.split_policy,
+                },
+                None,
+                variant.factorization,
+                config.num_threads,
+                config.tree_num_threads,
+                variant.tree_layout,
+                config.log_execution,
+                config.log_fact,
+                config.log_trees_and_suffix_array,
             );
 
             // VERIFICATION
             {
-                let suffix_array = innovative_suffix_array_computation.suffix_array;
-                let mut success = true;
-                if suffix_array.len() != classic_suffix_array.len() {
-                    success = false;
-                    println!("Computed Suffix Array is insufficient in size");
-                } else {
-                    let mut i = 0;
-                    while i < classic_suffix_array.len() {
-                        let clas_sa_item = classic_suffix_array[i];
-                        let inn_sa_item = suffix_array[i];
-                        if inn_sa_item != clas_sa_item {
-                            println!("Computed Suffix Array is insufficient: element [{}] should be \"{}\" but is \"{}\"", i, clas_sa_item, inn_sa_item);
-                            success = false;
-                        }
-                        i += 1;
-                    }
-                }
-                if !success {
+                let suffix_array = &innovative_suffix_array_computation.suffix_array;
+                let report = verify_suffix_array(str.as_bytes(), suffix_array);
+                if !report.is_ok() {
                     println!(" > Suffix Array: {:?}", suffix_array);
-                    println!("Computed Suffix Array is WRONG!!! :(");
+                    println!("Computed {} :(", report);
                     break;
                 }
             }
 
-            let et = &innovative_suffix_array_computation
-                .execution_info
-                .execution_timing;
-            sum_innovative_micros_vec[i].0 += et.p1_fact.dur.as_micros() as u64;
-            sum_innovative_micros_vec[i].1 += et.p2_tree.dur.as_micros() as u64;
-            sum_innovative_micros_vec[i].2 += et.p3_sa.dur.as_micros() as u64;
-            i += 1;
+            if !is_warm_up {
+                let et = &innovative_suffix_array_computation
+                    .execution_info
+                    .execution_timing;
+                innovative_samples_vec[i].push(
+                    et.p1_fact.dur.as_micros() as u64,
+                    et.p2_tree.dur.as_micros() as u64,
+                    et.p3_sa.dur.as_micros() as u64,
+                );
+            }
+            factorization_stats_vec[i] =
+                Some(innovative_suffix_array_computation.factorization_stats);
+            let execution_info = innovative_suffix_array_computation.execution_info;
+            memory_usage_vec[i] = Some(execution_info.memory_usage);
+            execution_outcome_vec[i] = Some(execution_info.execution_outcome);
+        }
+
+        // INNOVATIVE SUFFIX ARRAY WITH AUTOMATIC CHUNK SIZE
+        if let Some(auto_chunk_size) = &config.auto_chunk_size {
+            for (i_variant, variant) in variants.iter().enumerate() {
+                let innovative_suffix_array_computation = compute_innovative_suffix_array(
+                    &config.get_auto_results_folder(variant, auto_chunk_size),
+                    fasta_file_name,
+                    str.as_bytes(),
+                    Chunking {
+                        chunk_size: None,
+                        threshold: config.chunk_threshold,
+                        split_policy: variant.split_policy,
+                    },
+                    Some(*auto_chunk_size),
+                    variant.factorization,
+                    config.num_threads,
+                    config.tree_num_threads,
+                    variant.tree_layout,
+                    config.log_execution,
+                    config.log_fact,
+                    config.log_trees_and_suffix_array,
+                );
+                let report = verify_suffix_array(
+                    str.as_bytes(),
+                    &innovative_suffix_array_computation.suffix_array,
+                );
+                if !report.is_ok() {
+                    println!("Auto Chunk Size: computed {} :(", report);
+                }
+
+                if !is_warm_up {
+                    let et = &innovative_suffix_array_computation
+                        .execution_info
+                        .execution_timing;
+                    auto_samples_vec[i_variant].push(
+                        et.p1_fact.dur.as_micros() as u64,
+                        et.p2_tree.dur.as_micros() as u64,
+                        et.p3_sa.dur.as_micros() as u64,
+                    );
+                }
+                auto_selected_chunk_size_vec[i_variant] =
+                    innovative_suffix_array_computation.chunk_size;
+                auto_factorization_stats_vec[i_variant] =
+                    Some(innovative_suffix_array_computation.factorization_stats);
+            }
         }
     }
 
-    // CALCULATING MEANS AND PRINTING
-    println!("CLASSIC SUFFIX ARRAY CALCULATION");
-    let mean_classic_micros = (sum_classic_micros as f32 / num_attempts as f32) as u64;
-    print_duration(" > Sorting GSs duration   ", mean_classic_micros);
-    println!("INNOVATIVE SUFFIX ARRAY CALCULATION");
-    let mut chunk_size_and_phase_micros_list = Vec::new();
-    let mut i = 0;
-    for &chunk_size in chunk_size_vec {
-        let sum_micros = &sum_innovative_micros_vec[i];
-        let micros = (
-            (sum_micros.0 as f32 / num_attempts as f32) as u64,
-            (sum_micros.1 as f32 / num_attempts as f32) as u64,
-            (sum_micros.2 as f32 / num_attempts as f32) as u64,
+    // CALCULATING STATISTICS AND PRINTING
+    let outlier_iqr_factor = config.outlier_iqr_factor;
+    let plot_statistic = &config.plot_statistic;
+    println!("BASELINE SUFFIX ARRAY CALCULATION");
+    let mut baseline_stats_list = Vec::new();
+    for (i, &baseline) in baselines.iter().enumerate() {
+        let baseline_stats = compute_duration_stats(&baseline_samples_vec[i], outlier_iqr_factor);
+        print_duration_stats(
+            &format!(" > {:23}", baseline.get_label()),
+            &baseline_stats,
+            plot_statistic,
         );
+        baseline_stats_list.push(SuiteBaselineStatsFileFormat {
+            baseline,
+            duration: baseline_stats,
+        });
+    }
+    println!("INNOVATIVE SUFFIX ARRAY CALCULATION");
+    let mut chunk_size_stats_list = Vec::new();
+    let mut chunk_size_and_phase_micros_lists = vec![Vec::new(); variants.len()];
+    let mut chunk_size_and_memory_bytes_lists = vec![Vec::new(); variants.len()];
+    for (i, &(chunk_size, variant)) in innovative_runs.iter().enumerate() {
+        let i_variant = i % variants.len();
+        let samples = &innovative_samples_vec[i];
         let chunk_size_or_zero = chunk_size.unwrap_or(0);
-        println!("[CHUNK SIZE={chunk_size_or_zero}]");
-        print_duration(" > Phase 1: Factorization ", micros.0);
-        print_duration(" > Phase 2: Prefix Tree   ", micros.1);
-        print_duration(" > Phase 3: Suffix Array  ", micros.2);
-        chunk_size_and_phase_micros_list.push((chunk_size_or_zero, micros));
-        i += 1;
+        let chunk_size_stats = SuiteChunkSizeStatsFileFormat {
+            chunk_size: chunk_size_or_zero,
+            factorization: variant.factorization,
+            split_policy: variant.split_policy,
+            tree_layout: variant.tree_layout,
+            phase_1_fact: compute_duration_stats(&samples.p1_fact, outlier_iqr_factor),
+            phase_2_tree: compute_duration_stats(&samples.p2_tree, outlier_iqr_factor),
+            phase_3_sa__: compute_duration_stats(&samples.p3_sa, outlier_iqr_factor),
+            phases_total: compute_duration_stats(&samples.total, outlier_iqr_factor),
+            memory: memory_usage_vec[i].as_ref().map(MemoryUsageFileFormat::new),
+            outcome: execution_outcome_vec[i]
+                .as_ref()
+                .map(ExecutionOutcomeFileFormat::new),
+            factors: factorization_stats_vec[i].take(),
+        };
+        if variants.len() > 1 {
+            println!(
+                "[CHUNK SIZE={chunk_size_or_zero}, VARIANT={}]",
+                variant.get_label()
+            );
+        } else {
+            println!("[CHUNK SIZE={chunk_size_or_zero}]");
+        }
+        print_duration_stats(
+            " > Phase 1: Factorization ",
+            &chunk_size_stats.phase_1_fact,
+            plot_statistic,
+        );
+        print_duration_stats(
+            " > Phase 2: Prefix Tree   ",
+            &chunk_size_stats.phase_2_tree,
+            plot_statistic,
+        );
+        print_duration_stats(
+            " > Phase 3: Suffix Array  ",
+            &chunk_size_stats.phase_3_sa__,
+            plot_statistic,
+        );
+        print_duration_stats(
+            " > Phases Total           ",
+            &chunk_size_stats.phases_total,
+            plot_statistic,
+        );
+        if let Some(memory_usage) = &memory_usage_vec[i] {
+            println!(
+                " > Memory                 : nodes={}, rankings={}, aux rankings peak={}, peak RSS={}",
+                memory_usage.tree_nodes,
+                memory_usage.tree_ranking_entries,
+                memory_usage.aux_ranking_entries_peak,
+                memory_usage
+                    .peak_rss_kb
+                    .map_or("-".to_string(), |kb| format!("{} kB", kb)),
+            );
+            chunk_size_and_memory_bytes_lists[i_variant].push((
+                chunk_size_or_zero,
+                (
+                    memory_usage.tree_nodes_bytes as u64,
+                    memory_usage.get_tree_rankings_bytes() as u64,
+                    memory_usage.get_aux_rankings_peak_bytes() as u64,
+                ),
+            ));
+        }
+        if let Some(execution_outcome) = &execution_outcome_vec[i] {
+            println!(
+                " > Compares               : rules={}, strcmp={}",
+                execution_outcome.compares_using_rules, execution_outcome.compares_using_strcmp,
+            );
+        }
+        if let Some(factorization_stats) = &chunk_size_stats.factors {
+            print_factorization_stats(factorization_stats);
+        }
+        chunk_size_and_phase_micros_lists[i_variant].push((
+            chunk_size_or_zero,
+            (
+                chunk_size_stats.phase_1_fact.get(plot_statistic),
+                chunk_size_stats.phase_2_tree.get(plot_statistic),
+                chunk_size_stats.phase_3_sa__.get(plot_statistic),
+            ),
+            Some(chunk_size_stats.phases_total.get_error_bar(plot_statistic)),
+        ));
+        chunk_size_stats_list.push(chunk_size_stats);
     }
 
-    // PLOT
-    if draw_plot {
-        draw_plot_from_monitor(
+    let mut auto_stats_list = Vec::new();
+    if let Some(auto_chunk_size) = config.auto_chunk_size {
+        println!(
+            "INNOVATIVE SUFFIX ARRAY CALCULATION WITH AUTO CHUNK SIZE ({})",
+            auto_chunk_size.get_label()
+        );
+        for (i_variant, variant) in variants.iter().enumerate() {
+            // Best of the Chunk Sizes of the Suite, for the same Variant.
+            let Some(best_chunk_size_stats) = chunk_size_stats_list
+                .iter()
+                .skip(i_variant)
+                .step_by(variants.len())
+                .min_by_key(|chunk_size_stats| chunk_size_stats.phases_total.get(plot_statistic))
+            else {
+                continue;
+            };
+            let samples = &auto_samples_vec[i_variant];
+            let selected_chunk_size_or_zero = auto_selected_chunk_size_vec[i_variant].unwrap_or(0);
+            let phases_total = compute_duration_stats(&samples.total, outlier_iqr_factor);
+            let best_phases_total = best_chunk_size_stats.phases_total.get(plot_statistic);
+            let gap_percentage = (phases_total.get(plot_statistic) as f64
+                - best_phases_total as f64)
+                / best_phases_total.max(1) as f64
+                * 100.0;
+            let auto_stats = SuiteAutoChunkSizeStatsFileFormat {
+                auto_chunk_size,
+                factorization: variant.factorization,
+                split_policy: variant.split_policy,
+                tree_layout: variant.tree_layout,
+                selected_chunk_size: selected_chunk_size_or_zero,
+                phase_1_fact: compute_duration_stats(&samples.p1_fact, outlier_iqr_factor),
+                phase_2_tree: compute_duration_stats(&samples.p2_tree, outlier_iqr_factor),
+                phase_3_sa__: compute_duration_stats(&samples.p3_sa, outlier_iqr_factor),
+                phases_total,
+                best_chunk_size: best_chunk_size_stats.chunk_size,
+                best_phases_total,
+                gap_percentage,
+                factors: auto_factorization_stats_vec[i_variant].take(),
+            };
+            if variants.len() > 1 {
+                println!(
+                    "[CHUNK SIZE={selected_chunk_size_or_zero} (AUTO), VARIANT={}]",
+                    variant.get_label()
+                );
+            } else {
+                println!("[CHUNK SIZE={selected_chunk_size_or_zero} (AUTO)]");
+            }
+            print_duration_stats(
+                " > Phase 1: Factorization ",
+                &auto_stats.phase_1_fact,
+                plot_statistic,
+            );
+            print_duration_stats(
+                " > Phase 2: Prefix Tree   ",
+                &auto_stats.phase_2_tree,
+                plot_statistic,
+            );
+            print_duration_stats(
+                " > Phase 3: Suffix Array  ",
+                &auto_stats.phase_3_sa__,
+                plot_statistic,
+            );
+            print_duration_stats(
+                " > Phases Total           ",
+                &auto_stats.phases_total,
+                plot_statistic,
+            );
+            if let Some(factorization_stats) = &auto_stats.factors {
+                print_factorization_stats(factorization_stats);
+            }
+            println!(
+                " > Best Chunk Size        : {} with {} micros, gap {:+.1}%",
+                auto_stats.best_chunk_size, auto_stats.best_phases_total, auto_stats.gap_percentage,
+            );
+            auto_stats_list.push(auto_stats);
+        }
+    }
+
+    let baseline_computations: Vec<_> = baseline_stats_list
+        .iter()
+        .map(|baseline_stats| {
+            (
+                baseline_stats.duration.get(plot_statistic),
+                Some(baseline_stats.duration.get_error_bar(plot_statistic)),
+            )
+        })
+        .collect();
+
+    // STATISTICS FILE
+    if config.log_execution {
+        make_sure_directory_exist(get_path_for_project_folder(
+            &config.results_folder,
             fasta_file_name,
-            mean_classic_micros,
-            chunk_size_and_phase_micros_list,
-            max_duration_in_micros,
+        ));
+        dump_json_in_file(
+            &SuiteStatsFileFormat {
+                fasta_file_name: fasta_file_name.to_string(),
+                num_attempts,
+                num_warm_up_attempts,
+                num_threads: config.num_threads,
+                tree_num_threads: config.tree_num_threads,
+                outlier_iqr_factor,
+                chunk_threshold: config.chunk_threshold,
+                baselines: baseline_stats_list,
+                innovative: chunk_size_stats_list,
+                innovative_auto: auto_stats_list,
+            },
+            get_path_for_project_suite_stats_file_json(
+                &config.results_folder,
+                fasta_file_name,
+                &config.get_run_name(),
+            ),
         );
     }
+
+    // PLOT
+    if config.draw_plot {
+        // A plot per Variant, with the same Baselines.
+        for (i_variant, variant) in variants.iter().enumerate() {
+            let plot_name = config.get_plot_name(variant);
+            draw_plot_with_error_bars(
+                &config.plots_folder,
+                &plot_name,
+                baseline_computations.clone(),
+                chunk_size_and_phase_micros_lists[i_variant].clone(),
+                config.max_duration_in_micros,
+            );
+            let chunk_size_and_memory_bytes_list = &chunk_size_and_memory_bytes_lists[i_variant];
+            if !chunk_size_and_memory_bytes_list.is_empty() {
+                draw_memory_plot(
+                    &config.plots_folder,
+                    &plot_name,
+                    chunk_size_and_memory_bytes_list.clone(),
+                );
+            }
+        }
+    }
+}
+
+struct PhaseSamples {
+    p1_fact: Vec<u64>,
+    p2_tree: Vec<u64>,
+    p3_sa: Vec<u64>,
+    total: Vec<u64>,
+}
+impl PhaseSamples {
+    fn new(capacity: usize) -> Self {
+        Self {
+            p1_fact: Vec::with_capacity(capacity),
+            p2_tree: Vec::with_capacity(capacity),
+            p3_sa: Vec::with_capacity(capacity),
+            total: Vec::with_capacity(capacity),
+        }
+    }
+    fn push(&mut self, p1_fact: u64, p2_tree: u64, p3_sa: u64) {
+        self.p1_fact.push(p1_fact);
+        self.p2_tree.push(p2_tree);
+        self.p3_sa.push(p3_sa);
+        self.total.push(p1_fact + p2_tree + p3_sa);
+    }
 }
 
-fn print_duration(prefix: &str, micros: u64) {
+pub fn print_duration(prefix: &str, micros: u64) {
     let duration = Duration::from_micros(micros);
     println!(
         "{}: {:10} micros / {:10.3} seconds",
@@ -128,3 +468,34 @@ fn print_duration(prefix: &str, micros: u64) {
         duration.as_secs_f64()
     );
 }
+
+fn print_factorization_stats(factorization_stats: &FactorizationStats) {
+    println!(
+        " > Factors                : icfl={}, total={}, custom={}, max size={}, mean size={:.1}, custom positions={:.1}%",
+        factorization_stats.num_icfl_factors,
+        factorization_stats.num_factors,
+        factorization_stats.num_custom_factors,
+        factorization_stats.max_factor_size,
+        factorization_stats.mean_factor_size,
+        factorization_stats.custom_positions_fraction * 100.0,
+    );
+}
+
+pub fn print_duration_stats(prefix: &str, stats: &DurationStats, statistic: &PlotStatistic) {
+    print_duration(prefix, stats.get(statistic));
+    println!(
+        "{}  min={} p5={:.0} p25={:.0} median={:.0} p75={:.0} p95={:.0} max={} mean={:.1} std_dev={:.1} (samples={}, rejected={})",
+        " ".repeat(prefix.len()),
+        stats.min,
+        stats.p5,
+        stats.p25,
+        stats.median,
+        stats.p75,
+        stats.p95,
+        stats.max,
+        stats.mean,
+        stats.std_dev,
+        stats.num_samples,
+        stats.num_rejected,
+    );
+}
@@ -0,0 +1,390 @@
+use crate::cli::chunk_sizes::parse_chunk_sizes;
+use crate::factorization::auto_chunk_size::AutoChunkSize;
+use crate::factorization::custom_factorization::SplitPolicy;
+use crate::factorization::factorizer::FactorizationStrategy;
+use crate::files::fasta::{FastaRecordSelection, FastaSelection};
+use crate::files::paths::{
+    get_path_in_generated_folder, DEFAULT_PLOTS_FOLDER, DEFAULT_RESULTS_FOLDER,
+};
+use crate::prefix_tree::layout::TreeLayout;
+use crate::suffix_array::baseline::BaselineSaca;
+use crate::suite_stats::PlotStatistic;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufReader;
+
+// SUITE CONFIGURATION
+pub struct SuiteConfig {
+    pub fasta_file_name: String, // Used to name results and plots.
+    pub input_file: String,
+    pub fasta_selection: FastaSelection, // Records of "input_file" that make the string.
+    pub chunk_size_vec: Vec<Option<usize>>,
+    pub chunk_threshold: Option<usize>, // Only longer Factors are chunked.
+    pub auto_chunk_size: Option<AutoChunkSize>, // Compared with the best of "chunk_size_vec".
+    pub baselines: Vec<BaselineSaca>,
+    pub num_attempts: usize,
+    pub num_warm_up_attempts: usize, // Run before the attempts, samples are discarded.
+    pub outlier_iqr_factor: Option<f64>, // Tukey's fences, usually 1.5.
+    pub num_threads: usize,          // Phase 3 threads.
+    pub tree_num_threads: usize,     // Phase 2 threads.
+    pub tree_layouts: Vec<TreeLayout>,
+    pub factorizations: Vec<FactorizationStrategy>,
+    pub split_policies: Vec<SplitPolicy>,
+    pub max_duration_in_micros: u32, // Plot height.
+    pub plot_statistic: PlotStatistic,
+    pub log_execution: bool,
+    pub log_fact: bool,
+    pub log_trees_and_suffix_array: bool,
+    pub draw_plot: bool,
+    pub results_folder: String,
+    pub plots_folder: String,
+}
+impl SuiteConfig {
+    pub fn new(fasta_file_name: &str, chunk_size_vec: Vec<Option<usize>>) -> Self {
+        Self {
+            fasta_file_name: fasta_file_name.to_string(),
+            input_file: get_path_in_generated_folder(fasta_file_name),
+            fasta_selection: FastaSelection::default(),
+            chunk_size_vec,
+            chunk_threshold: None,
+            auto_chunk_size: None,
+            baselines: vec![BaselineSaca::Classic],
+            num_attempts: 1,
+            num_warm_up_attempts: 0,
+            outlier_iqr_factor: None,
+            num_threads: 1,
+            tree_num_threads: 1,
+            tree_layouts: vec![TreeLayout::Nested],
+            factorizations: vec![FactorizationStrategy::Icfl],
+            split_policies: vec![SplitPolicy::RemainderFirst],
+            max_duration_in_micros: 1_000_000,
+            plot_statistic: PlotStatistic::Mean,
+            log_execution: false,
+            log_fact: false,
+            log_trees_and_suffix_array: false,
+            draw_plot: true,
+            results_folder: DEFAULT_RESULTS_FOLDER.to_string(),
+            plots_folder: DEFAULT_PLOTS_FOLDER.to_string(),
+        }
+    }
+    pub fn get_run_name(&self) -> String {
+        // Names plots and statistics, runs with multiple threads are kept apart to compare them.
+        let mut run_name = self.fasta_file_name.clone();
+        if self.tree_num_threads > 1 {
+            run_name.push_str(&format!("-tree-threads-{}", self.tree_num_threads));
+        }
+        if self.num_threads > 1 {
+            run_name.push_str(&format!("-threads-{}", self.num_threads));
+        }
+        run_name
+    }
+    pub fn get_variants(&self) -> Vec<InnovativeVariant> {
+        // Every Factorization with every Split Policy and every Tree Layout.
+        let mut variants = Vec::new();
+        for &factorization in &self.factorizations {
+            for &split_policy in &self.split_policies {
+                for &tree_layout in &self.tree_layouts {
+                    variants.push(InnovativeVariant {
+                        factorization,
+                        split_policy,
+                        tree_layout,
+                    });
+                }
+            }
+        }
+        variants
+    }
+    pub fn get_plot_name(&self, variant: &InnovativeVariant) -> String {
+        // Each Variant has its own plots, the default one keeps the Run Name.
+        match variant.get_name() {
+            Some(variant_name) => format!("{}-{}", self.get_run_name(), variant_name),
+            None => self.get_run_name(),
+        }
+    }
+    pub fn get_results_folder(&self, variant: &InnovativeVariant) -> String {
+        // Logs of each Variant are kept apart, the default one keeps the Results Folder.
+        match variant.get_name() {
+            Some(variant_name) => format!("{}/{}", self.results_folder, variant_name),
+            None => self.results_folder.clone(),
+        }
+    }
+    pub fn get_auto_results_folder(
+        &self,
+        variant: &InnovativeVariant,
+        auto_chunk_size: &AutoChunkSize,
+    ) -> String {
+        // The selected Chunk Size could be one of "chunk_size_vec": logs are kept apart.
+        format!(
+            "{}/auto-{}",
+            self.get_results_folder(variant),
+            auto_chunk_size.get_label()
+        )
+    }
+    pub fn from_file(filepath: String) -> Result<Self, String> {
+        let file = File::open(&filepath)
+            .map_err(|e| format!("Unable to read suite config \"{}\": {}", filepath, e))?;
+        let file_format: SuiteConfigFileFormat = serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Unable to parse suite config \"{}\": {}", filepath, e))?;
+        file_format.into_suite_config()
+    }
+}
+
+// INNOVATIVE VARIANT
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InnovativeVariant {
+    pub factorization: FactorizationStrategy,
+    pub split_policy: SplitPolicy,
+    pub tree_layout: TreeLayout,
+}
+impl InnovativeVariant {
+    pub fn get_name(&self) -> Option<String> {
+        // None for the default Variant (ICFL with Nested Tree), like "fact-cfl-tree-arena" otherwise.
+        let mut parts = Vec::new();
+        if self.factorization != FactorizationStrategy::Icfl {
+            parts.push(format!("fact-{}", self.factorization.get_label()));
+        }
+        if self.split_policy != SplitPolicy::RemainderFirst {
+            parts.push(format!("split-{}", self.split_policy.get_label()));
+        }
+        if self.tree_layout != TreeLayout::Nested {
+            parts.push(format!("tree-{}", self.tree_layout.get_label()));
+        }
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join("-"))
+        }
+    }
+    pub fn get_label(&self) -> String {
+        format!(
+            "{}, {}, {}",
+            self.factorization.get_label(),
+            self.split_policy.get_label(),
+            self.tree_layout.get_label()
+        )
+    }
+}
+
+// From a JSON file like:
+// {
+//   "fasta_file_name": "002_70",
+//   "fasta": { "records": "all:$", "uppercase": true },
+//   "chunk_sizes": "2..9,10..100:10,none",
+//   "chunk_threshold": 100,
+//   "auto_chunk_size": "dry_run",
+//   "baselines": ["classic", "sa_is", "prefix_doubling", "dc3"],
+//   "num_attempts": 10,
+//   "num_warm_up_attempts": 2,
+//   "outlier_iqr_factor": 1.5,
+//   "num_threads": 4,
+//   "tree_num_threads": 4,
+//   "tree_layouts": ["nested", "arena"],
+//   "factorizations": ["icfl", "icfl_cfl"],
+//   "split_policies": ["remainder_first", "balanced"],
+//   "plot_max_duration_in_micros": 200000,
+//   "plot_statistic": "median",
+//   "logs": { "execution": true }
+// }
+// Missing fields take the defaults of "SuiteConfig::new", unknown ones are rejected.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SuiteConfigFileFormat {
+    fasta_file_name: String,
+    #[serde(default)]
+    input_file: Option<String>, // Default: "generated/{fasta_file_name}.fasta".
+    #[serde(default)]
+    fasta: SuiteConfigFileFormatFasta,
+    chunk_sizes: String, // Chunk Size Expression, see "cli::chunk_sizes".
+    #[serde(default)]
+    chunk_threshold: Option<usize>,
+    #[serde(default)]
+    auto_chunk_size: Option<AutoChunkSize>, // "heuristic" or "dry_run".
+    #[serde(default)]
+    baselines: Option<Vec<BaselineSaca>>, // Default: only "classic".
+    #[serde(default)]
+    num_attempts: Option<usize>,
+    #[serde(default)]
+    num_warm_up_attempts: Option<usize>,
+    #[serde(default)]
+    outlier_iqr_factor: Option<f64>,
+    #[serde(default)]
+    num_threads: Option<usize>,
+    #[serde(default)]
+    tree_num_threads: Option<usize>,
+    #[serde(default)]
+    tree_layouts: Option<Vec<TreeLayout>>, // Default: only "nested".
+    #[serde(default)]
+    factorizations: Option<Vec<FactorizationStrategy>>, // Default: only "icfl".
+    #[serde(default)]
+    split_policies: Option<Vec<SplitPolicy>>, // Default: only "remainder_first".
+    #[serde(default)]
+    plot_max_duration_in_micros: Option<u32>,
+    #[serde(default)]
+    plot_statistic: Option<PlotStatistic>, // "mean" (default) or "median".
+    #[serde(default)]
+    logs: SuiteConfigFileFormatLogs,
+    #[serde(default)]
+    draw_plot: Option<bool>,
+    #[serde(default)]
+    output: SuiteConfigFileFormatOutput,
+}
+#[derive(Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct SuiteConfigFileFormatFasta {
+    #[serde(default)]
+    records: Option<String>, // Record Selection, see "FastaRecordSelection::parse".
+    #[serde(default)]
+    uppercase: bool,
+}
+#[derive(Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct SuiteConfigFileFormatLogs {
+    #[serde(default)]
+    execution: bool,
+    #[serde(default)]
+    factorization: bool,
+    #[serde(default)]
+    trees_and_suffix_array: bool,
+}
+#[derive(Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct SuiteConfigFileFormatOutput {
+    #[serde(default)]
+    results_folder: Option<String>,
+    #[serde(default)]
+    plots_folder: Option<String>,
+}
+impl SuiteConfigFileFormat {
+    pub fn into_suite_config(self) -> Result<SuiteConfig, String> {
+        let chunk_size_vec = parse_chunk_sizes(&self.chunk_sizes)?;
+        let mut config = SuiteConfig::new(&self.fasta_file_name, chunk_size_vec);
+        config.chunk_threshold = self.chunk_threshold;
+        config.auto_chunk_size = self.auto_chunk_size;
+        if let Some(input_file) = self.input_file {
+            config.input_file = input_file;
+        }
+        if let Some(records) = self.fasta.records {
+            config.fasta_selection.records = FastaRecordSelection::parse(&records)?;
+        }
+        config.fasta_selection.uppercase = self.fasta.uppercase;
+        if let Some(baselines) = self.baselines {
+            config.baselines = baselines;
+        }
+        if let Some(num_attempts) = self.num_attempts {
+            config.num_attempts = num_attempts;
+        }
+        if let Some(num_warm_up_attempts) = self.num_warm_up_attempts {
+            config.num_warm_up_attempts = num_warm_up_attempts;
+        }
+        config.outlier_iqr_factor = self.outlier_iqr_factor;
+        if let Some(num_threads) = self.num_threads {
+            config.num_threads = num_threads.max(1);
+        }
+        if let Some(tree_num_threads) = self.tree_num_threads {
+            config.tree_num_threads = tree_num_threads.max(1);
+        }
+        if let Some(tree_layouts) = self.tree_layouts {
+            if tree_layouts.is_empty() {
+                return Err("At least one tree layout is required".to_string());
+            }
+            config.tree_layouts = tree_layouts;
+        }
+        if let Some(factorizations) = self.factorizations {
+            if factorizations.is_empty() {
+                return Err("At least one factorization is required".to_string());
+            }
+            config.factorizations = factorizations;
+        }
+        if let Some(split_policies) = self.split_policies {
+            if split_policies.is_empty() {
+                return Err("At least one split policy is required".to_string());
+            }
+            config.split_policies = split_policies;
+        }
+        if let Some(max_duration_in_micros) = self.plot_max_duration_in_micros {
+            config.max_duration_in_micros = max_duration_in_micros;
+        }
+        if let Some(plot_statistic) = self.plot_statistic {
+            config.plot_statistic = plot_statistic;
+        }
+        config.log_execution = self.logs.execution;
+        config.log_fact = self.logs.factorization;
+        config.log_trees_and_suffix_array = self.logs.trees_and_suffix_array;
+        if let Some(draw_plot) = self.draw_plot {
+            config.draw_plot = draw_plot;
+        }
+        if let Some(results_folder) = self.output.results_folder {
+            config.results_folder = results_folder;
+        }
+        if let Some(plots_folder) = self.output.plots_folder {
+            config.plots_folder = plots_folder;
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Result<SuiteConfig, String> {
+        serde_json::from_str::<SuiteConfigFileFormat>(json)
+            .map_err(|e| e.to_string())?
+            .into_suite_config()
+    }
+
+    #[test]
+    fn load_checked_in_suites() {
+        for entry in std::fs::read_dir("suites").unwrap() {
+            let filepath = entry.unwrap().path().to_str().unwrap().to_string();
+            if let Err(message) = SuiteConfig::from_file(filepath) {
+                panic!("{}", message);
+            }
+        }
+    }
+
+    #[test]
+    fn load_minimal_config() {
+        let config = parse(r#"{ "fasta_file_name": "000", "chunk_sizes": "3,none" }"#).unwrap();
+        assert_eq!(config.chunk_size_vec, vec![Some(3), None]);
+        assert_eq!(config.fasta_selection, FastaSelection::default());
+        assert_eq!(config.plot_statistic, PlotStatistic::Mean);
+    }
+
+    #[test]
+    fn load_fasta_selection() {
+        let config = parse(
+            r#"{ "fasta_file_name": "000", "chunk_sizes": "3", "fasta": { "records": "all:$", "uppercase": true } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.fasta_selection,
+            FastaSelection::new(FastaRecordSelection::Concatenate(Some('$'))).uppercase(true)
+        );
+        assert!(parse(
+            r#"{ "fasta_file_name": "000", "chunk_sizes": "3", "fasta": { "records": "first" } }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn reject_unknown_fields() {
+        assert!(
+            parse(r#"{ "fasta_file_name": "000", "chunk_sizes": "3", "num_attempt": 2 }"#).is_err()
+        );
+        assert!(parse(
+            r#"{ "fasta_file_name": "000", "chunk_sizes": "3", "logs": { "executions": true } }"#
+        )
+        .is_err());
+        assert!(parse(
+            r#"{ "fasta_file_name": "000", "chunk_sizes": "3", "output": { "plot_folder": "x" } }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn reject_zero_chunk_size() {
+        assert!(parse(r#"{ "fasta_file_name": "000", "chunk_sizes": "0" }"#).is_err());
+        assert!(parse(r#"{ "fasta_file_name": "000", "chunk_sizes": "0..4,none" }"#).is_err());
+    }
+}
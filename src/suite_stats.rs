@@ -0,0 +1,286 @@
+use crate::factorization::auto_chunk_size::AutoChunkSize;
+use crate::factorization::custom_factorization::SplitPolicy;
+use crate::factorization::factorizer::FactorizationStrategy;
+use crate::factorization::stats::FactorizationStats;
+use crate::prefix_tree::layout::TreeLayout;
+use crate::prefix_tree::log_execution_outcome::ExecutionOutcomeFileFormat;
+use crate::prefix_tree::log_memory_usage::MemoryUsageFileFormat;
+use crate::suffix_array::baseline::BaselineSaca;
+use serde::{Deserialize, Serialize};
+
+// STATISTICS OVER THE SAMPLES OF MULTIPLE ATTEMPTS
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DurationStats {
+    pub num_samples: usize, // Samples kept, after outlier rejection.
+    pub num_rejected: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub median: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p95: f64,
+}
+impl DurationStats {
+    pub fn get(&self, statistic: &PlotStatistic) -> u64 {
+        match statistic {
+            PlotStatistic::Mean => self.mean as u64,
+            PlotStatistic::Median => self.median as u64,
+        }
+    }
+    pub fn get_error_bar(&self, statistic: &PlotStatistic) -> (u64, u64) {
+        // Mean: one standard deviation; Median: interquartile range.
+        match statistic {
+            PlotStatistic::Mean => (
+                (self.mean - self.std_dev).max(0.0) as u64,
+                (self.mean + self.std_dev) as u64,
+            ),
+            PlotStatistic::Median => (self.p25 as u64, self.p75 as u64),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PlotStatistic {
+    Mean,
+    Median,
+}
+impl PlotStatistic {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "mean" => Ok(PlotStatistic::Mean),
+            "median" => Ok(PlotStatistic::Median),
+            _ => Err(format!(
+                "Invalid plot statistic \"{}\", expected \"mean\" or \"median\"",
+                value
+            )),
+        }
+    }
+}
+
+pub fn compute_duration_stats(samples: &[u64], outlier_iqr_factor: Option<f64>) -> DurationStats {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let num_all_samples = sorted.len();
+
+    // Outlier Rejection (Tukey's fences): only samples in "[Q1 - k*IQR, Q3 + k*IQR]" are kept.
+    if let Some(k) = outlier_iqr_factor {
+        if sorted.len() >= 4 {
+            let q1 = get_percentile(&sorted, 25.0);
+            let q3 = get_percentile(&sorted, 75.0);
+            let iqr = q3 - q1;
+            let (low, high) = (q1 - k * iqr, q3 + k * iqr);
+            sorted.retain(|&sample| low <= sample as f64 && sample as f64 <= high);
+        }
+    }
+
+    let n = sorted.len();
+    if n == 0 {
+        return DurationStats {
+            num_samples: 0,
+            num_rejected: num_all_samples,
+            min: 0,
+            max: 0,
+            mean: 0.0,
+            std_dev: 0.0,
+            median: 0.0,
+            p5: 0.0,
+            p25: 0.0,
+            p75: 0.0,
+            p95: 0.0,
+        };
+    }
+    let mean = sorted.iter().map(|&sample| sample as f64).sum::<f64>() / n as f64;
+    let variance = if n > 1 {
+        // Sample variance (Bessel's correction).
+        sorted
+            .iter()
+            .map(|&sample| (sample as f64 - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1) as f64
+    } else {
+        0.0
+    };
+    DurationStats {
+        num_samples: n,
+        num_rejected: num_all_samples - n,
+        min: sorted[0],
+        max: sorted[n - 1],
+        mean,
+        std_dev: variance.sqrt(),
+        median: get_percentile(&sorted, 50.0),
+        p5: get_percentile(&sorted, 5.0),
+        p25: get_percentile(&sorted, 25.0),
+        p75: get_percentile(&sorted, 75.0),
+        p95: get_percentile(&sorted, 95.0),
+    }
+}
+
+fn get_percentile(sorted: &[u64], percentile: f64) -> f64 {
+    // Linear interpolation between the closest ranks.
+    let rank = percentile / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] as f64 * (1.0 - weight) + sorted[upper] as f64 * weight
+}
+
+// SUITE STATISTICS FILE
+#[derive(Serialize, Deserialize)]
+pub struct SuiteStatsFileFormat {
+    pub fasta_file_name: String,
+    pub num_attempts: usize,
+    pub num_warm_up_attempts: usize,
+    pub num_threads: usize,
+    pub tree_num_threads: usize,
+    pub outlier_iqr_factor: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_threshold: Option<usize>,
+    pub baselines: Vec<SuiteBaselineStatsFileFormat>,
+    pub innovative: Vec<SuiteChunkSizeStatsFileFormat>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub innovative_auto: Vec<SuiteAutoChunkSizeStatsFileFormat>,
+}
+#[derive(Serialize, Deserialize)]
+pub struct SuiteBaselineStatsFileFormat {
+    pub baseline: BaselineSaca,
+    pub duration: DurationStats,
+}
+#[derive(Serialize, Deserialize)]
+pub struct SuiteChunkSizeStatsFileFormat {
+    pub chunk_size: usize,
+    pub factorization: FactorizationStrategy,
+    pub split_policy: SplitPolicy,
+    pub tree_layout: TreeLayout,
+    pub phase_1_fact: DurationStats,
+    pub phase_2_tree: DurationStats,
+    pub phase_3_sa__: DurationStats,
+    pub phases_total: DurationStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryUsageFileFormat>, // From the last attempt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<ExecutionOutcomeFileFormat>, // From the last attempt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factors: Option<FactorizationStats>, // From the last attempt.
+}
+#[derive(Serialize, Deserialize)]
+pub struct SuiteAutoChunkSizeStatsFileFormat {
+    pub auto_chunk_size: AutoChunkSize,
+    pub factorization: FactorizationStrategy,
+    pub split_policy: SplitPolicy,
+    pub tree_layout: TreeLayout,
+    pub selected_chunk_size: usize, // From the last attempt, "0" for none.
+    pub phase_1_fact: DurationStats, // Selection included.
+    pub phase_2_tree: DurationStats,
+    pub phase_3_sa__: DurationStats,
+    pub phases_total: DurationStats,
+    pub best_chunk_size: usize, // Among the Chunk Sizes of the Suite.
+    pub best_phases_total: u64,
+    pub gap_percentage: f64, // Of "phases_total" over "best_phases_total".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factors: Option<FactorizationStats>, // From the last attempt.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(value: f64, expected: f64, name: &str) {
+        assert!(
+            (value - expected).abs() < 1e-9,
+            "{}: {} != {}",
+            name,
+            value,
+            expected
+        );
+    }
+    fn assert_percentiles(stats: &DurationStats, expected: [f64; 5]) {
+        // "[p5, p25, median, p75, p95]".
+        assert_close(stats.p5, expected[0], "p5");
+        assert_close(stats.p25, expected[1], "p25");
+        assert_close(stats.median, expected[2], "median");
+        assert_close(stats.p75, expected[3], "p75");
+        assert_close(stats.p95, expected[4], "p95");
+    }
+
+    #[test]
+    fn stats_of_odd_number_of_samples() {
+        let stats = compute_duration_stats(&[5, 1, 4, 2, 3], None);
+        assert_eq!((stats.num_samples, stats.num_rejected), (5, 0));
+        assert_eq!((stats.min, stats.max), (1, 5));
+        assert_close(stats.mean, 3.0, "mean");
+        assert_close(stats.std_dev, 2.5f64.sqrt(), "std_dev");
+        assert_percentiles(&stats, [1.2, 2.0, 3.0, 4.0, 4.8]);
+    }
+
+    #[test]
+    fn stats_of_even_number_of_samples() {
+        let stats = compute_duration_stats(&[4, 3, 2, 1], None);
+        assert_eq!((stats.num_samples, stats.num_rejected), (4, 0));
+        assert_eq!((stats.min, stats.max), (1, 4));
+        assert_close(stats.mean, 2.5, "mean");
+        assert_close(stats.std_dev, (5.0f64 / 3.0).sqrt(), "std_dev");
+        assert_percentiles(&stats, [1.15, 1.75, 2.5, 3.25, 3.85]);
+    }
+
+    #[test]
+    fn stats_of_equal_samples() {
+        // Zero IQR: the fences are the value itself, and nothing is rejected.
+        let stats = compute_duration_stats(&[7; 6], Some(1.5));
+        assert_eq!((stats.num_samples, stats.num_rejected), (6, 0));
+        assert_eq!((stats.min, stats.max), (7, 7));
+        assert_close(stats.mean, 7.0, "mean");
+        assert_close(stats.std_dev, 0.0, "std_dev");
+        assert_percentiles(&stats, [7.0; 5]);
+    }
+
+    #[test]
+    fn stats_of_single_sample() {
+        let stats = compute_duration_stats(&[42], Some(1.5));
+        assert_eq!((stats.num_samples, stats.num_rejected), (1, 0));
+        assert_eq!((stats.min, stats.max), (42, 42));
+        assert_close(stats.mean, 42.0, "mean");
+        assert_close(stats.std_dev, 0.0, "std_dev");
+        assert_percentiles(&stats, [42.0; 5]);
+    }
+
+    #[test]
+    fn stats_of_no_samples() {
+        let stats = compute_duration_stats(&[], Some(1.5));
+        assert_eq!((stats.num_samples, stats.num_rejected), (0, 0));
+    }
+
+    #[test]
+    fn stats_reject_outlier() {
+        // Q1 = 11, Q3 = 13: fences with factor 1.5 are [8, 16].
+        let samples = [12, 1000, 10, 13, 11];
+        let stats = compute_duration_stats(&samples, None);
+        assert_eq!((stats.num_samples, stats.num_rejected), (5, 0));
+        assert_eq!(stats.max, 1000);
+
+        let stats = compute_duration_stats(&samples, Some(1.5));
+        assert_eq!((stats.num_samples, stats.num_rejected), (4, 1));
+        assert_eq!((stats.min, stats.max), (10, 13));
+        assert_close(stats.mean, 11.5, "mean");
+        assert_percentiles(&stats, [10.15, 10.75, 11.5, 12.25, 12.85]);
+    }
+
+    #[test]
+    fn stats_tukey_fences() {
+        // Q1 = 4, Q3 = 8, IQR = 4: "20" is out of [0, 12] with factor 1, on the fence with 3.
+        let samples = [2, 4, 6, 8, 20];
+        let stats = compute_duration_stats(&samples, Some(1.0));
+        assert_eq!((stats.num_rejected, stats.max), (1, 8));
+        let stats = compute_duration_stats(&samples, Some(3.0));
+        assert_eq!((stats.num_rejected, stats.max), (0, 20));
+        // Low outliers too: Q1 = 96, Q3 = 100, fences with factor 1.5 are [90, 106].
+        let stats = compute_duration_stats(&[1, 96, 98, 100, 100], Some(1.5));
+        assert_eq!((stats.num_rejected, stats.min), (1, 96));
+        // Fewer than 4 samples are never rejected.
+        let stats = compute_duration_stats(&[1, 2, 1000], Some(1.5));
+        assert_eq!((stats.num_samples, stats.num_rejected), (3, 0));
+    }
+}
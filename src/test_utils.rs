@@ -0,0 +1,70 @@
+// TEST UTILS
+// Strings shared by the tests. Random ones come from a seeded generator, so that a failure can
+// be reproduced: the seed is printed when a check panics, and can be forced with the
+// "PTSACA_TEST_SEED" environment variable.
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::RangeInclusive;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+pub(crate) fn for_each_random_string<F: FnMut(&[u8], &mut StdRng)>(
+    seed: u64,
+    num_strings: usize,
+    lengths: RangeInclusive<usize>,
+    alphabet_sizes: RangeInclusive<u8>,
+    mut check: F,
+) {
+    // Strings over the first letters ("A", "B", ...); the generator is also given to the check,
+    // for any other random choice.
+    let seed = match std::env::var("PTSACA_TEST_SEED") {
+        Ok(value) => value.parse().expect("Invalid PTSACA_TEST_SEED"),
+        Err(_) => seed,
+    };
+    let mut rng = StdRng::seed_from_u64(seed);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        for _ in 0..num_strings {
+            let alphabet_size = rng.random_range(alphabet_sizes.clone());
+            let length = rng.random_range(lengths.clone());
+            let str = (0..length)
+                .map(|_| b'A' + rng.random_range(0..alphabet_size))
+                .collect::<Vec<_>>();
+            check(&str, &mut rng);
+        }
+    }));
+    if let Err(error) = result {
+        eprintln!(
+            "Random strings with seed {} (PTSACA_TEST_SEED={})",
+            seed, seed
+        );
+        resume_unwind(error);
+    }
+}
+
+pub(crate) fn get_repetitive_strings(max_length: usize) -> Vec<Vec<u8>> {
+    // Constant and periodic strings (also with a different last symbol), and Fibonacci words:
+    // the worst cases for comparisons and for the Factorizations.
+    let mut result = Vec::new();
+    for length in 1..=max_length {
+        result.push(vec![b'A'; length]);
+        for period in ["AB", "BA", "AAB", "ABB", "CAB", "DCAABCA"] {
+            let period = period.as_bytes();
+            let mut str = (0..length)
+                .map(|i| period[i % period.len()])
+                .collect::<Vec<_>>();
+            result.push(str.clone());
+            str[length - 1] = b'A';
+            result.push(str.clone());
+            str[length - 1] = b'Z';
+            result.push(str);
+        }
+    }
+    let mut fibonacci = (b"B".to_vec(), b"A".to_vec());
+    while fibonacci.1.len() < max_length {
+        let next = [fibonacci.1.clone(), fibonacci.0.clone()].concat();
+        fibonacci = (fibonacci.1, next);
+    }
+    for length in 1..=max_length {
+        result.push(fibonacci.1[..length].to_vec());
+    }
+    result
+}